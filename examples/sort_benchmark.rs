@@ -0,0 +1,61 @@
+use std::time::{Duration, Instant};
+
+use algorithms_and_data_structures::sort::{self, random::XorShift};
+
+const LENGTH: usize = 1_000_000;
+const ROUNDS: u32 = 5;
+const SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+fn time(input: &[u64], sorter: fn(&mut [u64])) -> Duration {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ROUNDS {
+        let mut array = input.to_vec();
+        let start = Instant::now();
+        sorter(array.as_mut_slice());
+        total += start.elapsed();
+
+        assert!(array.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    total / ROUNDS
+}
+
+// Run with `cargo run --release --example sort_benchmark`.
+fn main() {
+    let inputs: Vec<(&str, Vec<u64>)> = vec![
+        ("random", XorShift::with_seed(SEED).take(LENGTH).collect()),
+        (
+            "few unique",
+            XorShift::with_seed(SEED)
+                .take(LENGTH)
+                .map(|value| value % 16)
+                .collect(),
+        ),
+        ("sorted", (0..LENGTH as u64).collect()),
+        ("reversed", (0..LENGTH as u64).rev().collect()),
+        ("all equal", vec![7; LENGTH]),
+        (
+            "organ pipe",
+            (0..LENGTH as u64 / 2)
+                .chain((0..LENGTH as u64 / 2).rev())
+                .collect(),
+        ),
+        (
+            "sawtooth",
+            (0..LENGTH as u64).map(|value| value % 1_000).collect(),
+        ),
+    ];
+
+    println!(
+        "{:<12} {:>16} {:>20}",
+        "input", "sort::sort", "slice::sort_unstable"
+    );
+
+    for (name, input) in inputs {
+        let ours = time(&input, sort::sort);
+        let std = time(&input, <[u64]>::sort_unstable);
+
+        println!("{name:<12} {ours:>16.2?} {std:>20.2?}");
+    }
+}
@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use super::list_sort::{self, ListNode};
+use crate::sort::partial_compare;
 
 type Link<T> = Rc<RefCell<Node<T>>>;
 
@@ -18,6 +21,26 @@ impl<T> Node<T> {
     }
 }
 
+impl<T> ListNode for Node<T> {
+    type Value = T;
+
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn next(&self) -> &Option<Link<T>> {
+        &self.next
+    }
+
+    fn next_mut(&mut self) -> &mut Option<Link<T>> {
+        &mut self.next
+    }
+
+    fn set_previous(&mut self, previous: Option<Link<T>>) {
+        self.previous = previous;
+    }
+}
+
 pub struct DoublyLinkedList<T> {
     length: usize,
     head: Option<Link<T>>,
@@ -199,6 +222,26 @@ impl<T> DoublyLinkedList<T> {
     }
 }
 
+impl<T> DoublyLinkedList<T> {
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(partial_compare);
+    }
+
+    pub fn sort_by_key<K: PartialOrd>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| partial_compare(&key(a), &key(b)));
+    }
+
+    // A stable merge sort that relinks the nodes rather than moving values.
+    // Runs are merged along their `next` links only, and the `previous` links
+    // are rebuilt in a single pass at the end.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        list_sort::sort_by(&mut self.head, &mut self.tail, self.length, &mut compare);
+    }
+}
+
 impl<T> Drop for DoublyLinkedList<T> {
     fn drop(&mut self) {
         while let Some(node) = self.head.take() {
@@ -746,4 +789,125 @@ mod tests {
             assert!(linked_list.head.is_none());
         }
     }
+
+    mod sort {
+        use std::panic::{self, AssertUnwindSafe};
+
+        use super::*;
+        use crate::testing::pseudo_random;
+
+        #[test]
+        fn can_sort_empty_and_single_item_lists() {
+            let mut linked_list: DoublyLinkedList<u8> = DoublyLinkedList::new();
+            linked_list.sort();
+            assert!(linked_list.head.is_none());
+            assert!(linked_list.tail.is_none());
+
+            let mut linked_list = DoublyLinkedList::from(Vec::from([4]));
+            linked_list.sort();
+            assert_eq!(linked_list.collect::<Vec<_>>(), [4]);
+        }
+
+        #[test]
+        fn should_sort_random_values() {
+            for length in [2, 3, 10, 100, 1_001] {
+                let values = pseudo_random(length, 50);
+                let mut expected = values.clone();
+                expected.sort();
+
+                let mut linked_list = DoublyLinkedList::from(values);
+                linked_list.sort();
+
+                assert_eq!(linked_list.len(), length);
+                assert_eq!(linked_list.collect::<Vec<_>>(), expected);
+            }
+        }
+
+        #[test]
+        fn should_rebuild_previous_links() {
+            let values = pseudo_random(300, 1_000);
+            let mut expected = values.clone();
+            expected.sort();
+            expected.reverse();
+
+            let mut linked_list = DoublyLinkedList::from(values);
+            linked_list.sort();
+
+            assert!(linked_list
+                .head
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .previous
+                .is_none());
+            assert!(linked_list.tail.as_ref().unwrap().borrow().next.is_none());
+            let backwards: Vec<u64> = std::iter::from_fn(|| linked_list.pop()).collect();
+            assert_eq!(backwards, expected);
+        }
+
+        #[test]
+        fn should_keep_equal_keys_in_order() {
+            let values: Vec<(u64, usize)> = pseudo_random(500, 5)
+                .into_iter()
+                .enumerate()
+                .map(|(index, key)| (key, index))
+                .collect();
+            let mut expected = values.clone();
+            expected.sort_by_key(|(key, _)| *key);
+
+            let mut linked_list = DoublyLinkedList::from(values);
+            linked_list.sort_by_key(|(key, _)| *key);
+
+            assert_eq!(linked_list.collect::<Vec<_>>(), expected);
+        }
+
+        // The comparator gives up part way through, by which point the nodes
+        // are spread across the runs being merged. Walking back from the tail
+        // must still find every one of them once the panic is caught.
+        #[test]
+        fn should_keep_every_node_when_comparator_panics() {
+            let values = pseudo_random(100, 20);
+            let mut expected = values.clone();
+            expected.sort();
+
+            for limit in [0, 1, 50, 150, 400] {
+                let mut linked_list = DoublyLinkedList::from(values.clone());
+                let mut remaining = limit;
+
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    linked_list.sort_by(|a, b| {
+                        if remaining == 0 {
+                            panic::resume_unwind(Box::new("comparator gave up"));
+                        }
+                        remaining -= 1;
+                        a.cmp(b)
+                    })
+                }));
+                assert!(result.is_err());
+
+                assert_eq!(linked_list.len(), 100);
+                let mut actual: Vec<u64> = std::iter::from_fn(|| linked_list.pop()).collect();
+                actual.sort();
+                assert_eq!(actual, expected, "panicking after {limit} comparisons");
+                assert!(linked_list.head.is_none());
+            }
+        }
+
+        // Values that cannot be cloned can only be sorted by relinking.
+        #[test]
+        fn should_sort_values_that_are_not_clone() {
+            struct Boxed(Box<u64>);
+
+            let values: Vec<Boxed> = [10, 9, 22, 10, 15, 5, 20, 1, 2, 1]
+                .into_iter()
+                .map(|value| Boxed(Box::new(value)))
+                .collect();
+
+            let mut linked_list = DoublyLinkedList::from(values);
+            linked_list.sort_by(|a, b| b.0.cmp(&a.0));
+
+            let sorted: Vec<u64> = linked_list.map(|boxed| *boxed.0).collect();
+            assert_eq!(sorted, [22, 20, 15, 10, 10, 9, 5, 2, 1, 1]);
+        }
+    }
 }
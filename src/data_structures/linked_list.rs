@@ -1,4 +1,7 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, fmt::Debug, rc::Rc};
+
+use super::list_sort::{self, ListNode};
+use crate::sort::partial_compare;
 
 type Link<T> = Rc<RefCell<Node<T>>>;
 
@@ -14,6 +17,22 @@ impl<T: Clone> Node<T> {
     }
 }
 
+impl<T: Clone> ListNode for Node<T> {
+    type Value = T;
+
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn next(&self) -> &Option<Link<T>> {
+        &self.next
+    }
+
+    fn next_mut(&mut self) -> &mut Option<Link<T>> {
+        &mut self.next
+    }
+}
+
 #[derive(Debug)]
 pub struct SinglyLinkedList<T: Debug + Clone> {
     length: usize,
@@ -214,6 +233,25 @@ impl<T: Debug + Clone> SinglyLinkedList<T> {
     }
 }
 
+impl<T: Debug + Clone> SinglyLinkedList<T> {
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(partial_compare);
+    }
+
+    pub fn sort_by_key<K: PartialOrd>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| partial_compare(&key(a), &key(b)));
+    }
+
+    // A stable bottom-up merge sort that relinks the nodes rather than moving
+    // values, so nothing is cloned.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        list_sort::sort_by(&mut self.head, &mut self.tail, self.length, &mut compare);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,4 +496,122 @@ mod tests {
             assert_eq!(tail.value, 3);
         }
     }
+
+    mod sort {
+        use std::panic::{self, AssertUnwindSafe};
+
+        use super::*;
+        use crate::testing::pseudo_random;
+
+        fn drain<T: Debug + Clone>(linked_list: &mut SinglyLinkedList<T>) -> Vec<T> {
+            std::iter::from_fn(|| linked_list.shift()).collect()
+        }
+
+        #[test]
+        fn can_sort_empty_and_single_item_lists() {
+            let mut linked_list: SinglyLinkedList<u8> = SinglyLinkedList::new();
+            linked_list.sort();
+            assert!(linked_list.head.is_none());
+            assert!(linked_list.tail.is_none());
+
+            let mut linked_list = SinglyLinkedList::from(Vec::from([4]));
+            linked_list.sort();
+            assert_eq!(drain(&mut linked_list), [4]);
+        }
+
+        #[test]
+        fn should_sort_random_values_and_keep_tail() {
+            for length in [2, 3, 10, 100, 1_001] {
+                let values = pseudo_random(length, 50);
+                let mut expected = values.clone();
+                expected.sort();
+
+                let mut linked_list = SinglyLinkedList::from(values);
+                linked_list.sort();
+                linked_list.push(50);
+                expected.push(50);
+
+                assert_eq!(linked_list.len(), length + 1);
+                assert_eq!(drain(&mut linked_list), expected);
+            }
+        }
+
+        #[test]
+        fn should_keep_equal_keys_in_order() {
+            let values: Vec<(u64, usize)> = pseudo_random(500, 5)
+                .into_iter()
+                .enumerate()
+                .map(|(index, key)| (key, index))
+                .collect();
+            let mut expected = values.clone();
+            expected.sort_by_key(|(key, _)| *key);
+
+            let mut linked_list = SinglyLinkedList::from(values);
+            linked_list.sort_by_key(|(key, _)| *key);
+
+            assert_eq!(drain(&mut linked_list), expected);
+        }
+
+        #[test]
+        fn should_relink_nodes_instead_of_copying_values() {
+            let mut linked_list = SinglyLinkedList::from(Vec::from([3, 1, 2]));
+            let smallest = linked_list
+                .head
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .next
+                .clone()
+                .unwrap();
+
+            linked_list.sort_by(|a, b| a.cmp(b));
+
+            assert!(Rc::ptr_eq(linked_list.head.as_ref().unwrap(), &smallest));
+            assert_eq!(linked_list.tail.as_ref().unwrap().borrow().value, 3);
+            assert!(linked_list.tail.as_ref().unwrap().borrow().next.is_none());
+        }
+
+        // The comparator gives up part way through, by which point the nodes
+        // are spread across the runs being merged. They must all be back in
+        // the list, with its tail still at the end, once the panic is caught.
+        #[test]
+        fn should_keep_every_node_when_comparator_panics() {
+            let values = pseudo_random(100, 20);
+            let mut expected = values.clone();
+            expected.sort();
+            expected.push(20);
+
+            for limit in [0, 1, 50, 150, 400] {
+                let mut linked_list = SinglyLinkedList::from(values.clone());
+                let mut remaining = limit;
+
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    linked_list.sort_by(|a, b| {
+                        if remaining == 0 {
+                            panic::resume_unwind(Box::new("comparator gave up"));
+                        }
+                        remaining -= 1;
+                        a.cmp(b)
+                    })
+                }));
+                assert!(result.is_err());
+
+                linked_list.push(20);
+                assert_eq!(linked_list.len(), 101);
+                let mut actual = drain(&mut linked_list);
+                actual.sort();
+                assert_eq!(actual, expected, "panicking after {limit} comparisons");
+            }
+        }
+
+        #[test]
+        fn should_sort_by_descending_comparator() {
+            let mut linked_list =
+                SinglyLinkedList::from(Vec::from([10, 9, 22, 10, 15, 5, 20, 1, 2, 1]));
+
+            linked_list.sort_by(|a, b| b.cmp(a));
+
+            assert_eq!(drain(&mut linked_list), [22, 20, 15, 10, 10, 9, 5, 2, 1, 1]);
+        }
+    }
 }
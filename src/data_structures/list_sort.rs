@@ -0,0 +1,166 @@
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+// The merge sort behind `sort_by` on both linked lists. It relinks nodes
+// rather than moving values, so nothing is cloned, and only follows `next`
+// links, so the lists just have to say how to reach a node's value and links.
+
+pub(crate) type Link<N> = Rc<RefCell<N>>;
+
+pub(crate) trait ListNode: Sized {
+    type Value;
+
+    fn value(&self) -> &Self::Value;
+
+    fn next(&self) -> &Option<Link<Self>>;
+
+    fn next_mut(&mut self) -> &mut Option<Link<Self>>;
+
+    // Only nodes that also link backwards need this. It is called on every
+    // node, in order, once the sort has put the list back together.
+    fn set_previous(&mut self, _previous: Option<Link<Self>>) {}
+}
+
+const RUN: &str = "runs being merged are not empty";
+
+// Sorts the `length` nodes chained from `head` and points `head` and `tail`
+// at the new ends. Runs of one node, then two, then four and so on are merged
+// pairwise along the list, so the extra space is O(1).
+pub(crate) fn sort_by<N: ListNode>(
+    head: &mut Option<Link<N>>,
+    tail: &mut Option<Link<N>>,
+    length: usize,
+    compare: &mut impl FnMut(&N::Value, &N::Value) -> Ordering,
+) {
+    let mut sorting = Sorting {
+        merged: head.take().zip(tail.take()),
+        head,
+        tail,
+        left: None,
+        right: None,
+        rest: None,
+    };
+
+    let mut width = 1;
+    while width < length {
+        sorting.rest = sorting.merged.take().map(|(head, _)| head);
+
+        while let Some((left, left_tail)) = sorting.take_run(width) {
+            match sorting.take_run(width) {
+                Some((right, right_tail)) => {
+                    sorting.left = Some(left);
+                    sorting.right = Some(right);
+                    sorting.merge(left_tail, right_tail, compare);
+                }
+                None => sorting.append(left, left_tail),
+            }
+        }
+
+        width *= 2;
+    }
+}
+
+// Every node is somewhere in these chains while the sort runs: the runs
+// merged so far on this pass, what is left of the two runs being merged, and
+// the rest of the list. Dropping the guard strings them back together into
+// the list, so a comparator that panics leaves every node in it, in some
+// order, instead of losing the ones held by the sort.
+struct Sorting<'a, N: ListNode> {
+    head: &'a mut Option<Link<N>>,
+    tail: &'a mut Option<Link<N>>,
+    merged: Option<(Link<N>, Link<N>)>,
+    left: Option<Link<N>>,
+    right: Option<Link<N>>,
+    rest: Option<Link<N>>,
+}
+
+impl<N: ListNode> Sorting<'_, N> {
+    // Cuts up to `width` nodes off the front of `rest`, returning the first
+    // and last of them.
+    fn take_run(&mut self, width: usize) -> Option<(Link<N>, Link<N>)> {
+        let first = self.rest.take()?;
+
+        let mut last = first.clone();
+        for _ in 1..width {
+            let next = last.borrow().next().clone();
+            match next {
+                Some(next) => last = next,
+                None => break,
+            }
+        }
+
+        self.rest = last.borrow_mut().next_mut().take();
+        Some((first, last))
+    }
+
+    // Ties go to the left run, which keeps the sort stable.
+    fn merge(
+        &mut self,
+        left_tail: Link<N>,
+        right_tail: Link<N>,
+        compare: &mut impl FnMut(&N::Value, &N::Value) -> Ordering,
+    ) {
+        let right_head = self.right.clone().expect(RUN);
+        if !compare(right_head.borrow().value(), left_tail.borrow().value()).is_lt() {
+            *left_tail.borrow_mut().next_mut() = self.right.take();
+            let left = self.left.take().expect(RUN);
+            self.append(left, right_tail);
+            return;
+        }
+
+        while let (Some(left), Some(right)) = (&self.left, &self.right) {
+            let source = if compare(right.borrow().value(), left.borrow().value()).is_lt() {
+                &mut self.right
+            } else {
+                &mut self.left
+            };
+
+            let node = source.take().expect(RUN);
+            *source = node.borrow_mut().next_mut().take();
+            self.append(node.clone(), node);
+        }
+
+        match (self.left.take(), self.right.take()) {
+            (Some(left), _) => self.append(left, left_tail),
+            (_, Some(right)) => self.append(right, right_tail),
+            (None, None) => unreachable!("one run outlasts the other"),
+        }
+    }
+
+    fn append(&mut self, first: Link<N>, last: Link<N>) {
+        match &mut self.merged {
+            Some((_, tail)) => {
+                *tail.borrow_mut().next_mut() = Some(first);
+                *tail = last;
+            }
+            None => self.merged = Some((first, last)),
+        }
+    }
+}
+
+impl<N: ListNode> Drop for Sorting<'_, N> {
+    fn drop(&mut self) {
+        let chains = [
+            self.merged.take().map(|(head, _)| head),
+            self.left.take(),
+            self.right.take(),
+            self.rest.take(),
+        ];
+
+        let mut previous: Option<Link<N>> = None;
+        for chain in chains.into_iter().flatten() {
+            match &previous {
+                Some(last) => *last.borrow_mut().next_mut() = Some(chain.clone()),
+                None => *self.head = Some(chain.clone()),
+            }
+
+            let mut current = Some(chain);
+            while let Some(node) = current {
+                node.borrow_mut().set_previous(previous.take());
+                current = node.borrow().next().clone();
+                previous = Some(node);
+            }
+        }
+
+        *self.tail = previous;
+    }
+}
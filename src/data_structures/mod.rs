@@ -2,3 +2,5 @@ pub mod binary_search_tree;
 pub mod doubly_linked_list;
 pub mod linked_list;
 pub mod stack;
+
+mod list_sort;
@@ -1,6 +1,10 @@
 pub mod data_structures;
+pub mod search;
 pub mod sort;
 
+#[cfg(test)]
+mod testing;
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
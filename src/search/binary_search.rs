@@ -0,0 +1,186 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::sort::partial_compare;
+
+// The comparator variants take a closure that orders an element of the slice
+// against the target, returning `Less` for elements that belong before it. The
+// slice must be sorted consistently with that closure.
+
+pub fn binary_search<T: PartialOrd>(array: &[T], target: &T) -> Option<usize> {
+    binary_search_by(array, |element| partial_compare(element, target))
+}
+
+pub fn binary_search_by_key<T, K: PartialOrd>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> Option<usize> {
+    binary_search_by(array, |element| partial_compare(&f(element), key))
+}
+
+// Returns the index of the first element equal to the target, if any.
+pub fn binary_search_by<T>(array: &[T], mut compare: impl FnMut(&T) -> Ordering) -> Option<usize> {
+    let index = lower_bound_by(array, &mut compare);
+
+    (index < array.len() && compare(&array[index]).is_eq()).then_some(index)
+}
+
+pub fn lower_bound<T: PartialOrd>(array: &[T], target: &T) -> usize {
+    lower_bound_by(array, |element| partial_compare(element, target))
+}
+
+pub fn lower_bound_by_key<T, K: PartialOrd>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> usize {
+    lower_bound_by(array, |element| partial_compare(&f(element), key))
+}
+
+// Returns the index of the first element that is not less than the target,
+// which is where the target would be inserted to keep the slice sorted ahead
+// of any equal elements.
+pub fn lower_bound_by<T>(array: &[T], mut compare: impl FnMut(&T) -> Ordering) -> usize {
+    partition_point(array, |element| compare(element).is_lt())
+}
+
+pub fn upper_bound<T: PartialOrd>(array: &[T], target: &T) -> usize {
+    upper_bound_by(array, |element| partial_compare(element, target))
+}
+
+pub fn upper_bound_by_key<T, K: PartialOrd>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> usize {
+    upper_bound_by(array, |element| partial_compare(&f(element), key))
+}
+
+// Returns the index of the first element greater than the target.
+pub fn upper_bound_by<T>(array: &[T], mut compare: impl FnMut(&T) -> Ordering) -> usize {
+    partition_point(array, |element| compare(element).is_le())
+}
+
+pub fn equal_range<T: PartialOrd>(array: &[T], target: &T) -> Range<usize> {
+    equal_range_by(array, |element| partial_compare(element, target))
+}
+
+pub fn equal_range_by_key<T, K: PartialOrd>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> Range<usize> {
+    equal_range_by(array, |element| partial_compare(&f(element), key))
+}
+
+pub fn equal_range_by<T>(array: &[T], mut compare: impl FnMut(&T) -> Ordering) -> Range<usize> {
+    let start = lower_bound_by(array, &mut compare);
+    let end = start + upper_bound_by(&array[start..], &mut compare);
+
+    start..end
+}
+
+// Returns the number of leading elements for which `before` holds, given that
+// it holds for a prefix of the slice and fails for the rest.
+fn partition_point<T>(array: &[T], mut before: impl FnMut(&T) -> bool) -> usize {
+    let mut low = 0;
+    let mut high = array.len();
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if before(&array[middle]) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::sorter::registry;
+    use crate::testing::pseudo_random;
+
+    // Every sorter in the crate produces the input for at least one search, and
+    // every target in and around the value range is checked against a scan.
+    #[test]
+    fn searches_agree_with_linear_scan_over_sorted_output() {
+        for (index, sorter) in registry::<u64>().iter().enumerate() {
+            let mut array = pseudo_random(50 + 37 * index, 40);
+            sorter.sort(array.as_mut_slice());
+
+            for target in 0..=41 {
+                let lower = array.iter().filter(|value| **value < target).count();
+                let upper = array.iter().filter(|value| **value <= target).count();
+
+                assert_eq!(lower_bound(&array, &target), lower);
+                assert_eq!(upper_bound(&array, &target), upper);
+                assert_eq!(equal_range(&array, &target), lower..upper);
+                assert_eq!(
+                    binary_search(&array, &target),
+                    (lower < upper).then_some(lower)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_empty_and_single_element_slices() {
+        let empty: [u64; 0] = [];
+
+        assert_eq!(binary_search(&empty, &1), None);
+        assert_eq!(lower_bound(&empty, &1), 0);
+        assert_eq!(equal_range(&empty, &1), 0..0);
+        assert_eq!(binary_search(&[1], &1), Some(0));
+        assert_eq!(binary_search(&[1], &0), None);
+        assert_eq!(upper_bound(&[1], &1), 1);
+    }
+
+    #[test]
+    fn search_by_descending_comparator() {
+        let mut array = pseudo_random(500, 30);
+        crate::sort::sort_by(array.as_mut_slice(), |a, b| b.cmp(a));
+
+        for target in 0..30 {
+            let lower = array.iter().filter(|value| **value > target).count();
+            let upper = array.iter().filter(|value| **value >= target).count();
+
+            assert_eq!(
+                equal_range_by(&array, |element| target.cmp(element)),
+                lower..upper
+            );
+        }
+    }
+
+    #[test]
+    fn search_by_key_field() {
+        let mut array: Vec<(u64, usize)> = pseudo_random(300, 20)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        crate::sort::merge_sort::sort_by_key(array.as_mut_slice(), |(key, _)| *key);
+
+        for key in 0..20 {
+            let range = equal_range_by_key(&array, &key, |(key, _)| *key);
+
+            assert!(array[range.clone()].iter().all(|(found, _)| *found == key));
+            assert_eq!(
+                range.len(),
+                array.iter().filter(|(found, _)| *found == key).count()
+            );
+            assert_eq!(
+                binary_search_by_key(&array, &key, |(key, _)| *key),
+                (!range.is_empty()).then_some(range.start)
+            );
+            assert_eq!(
+                lower_bound_by_key(&array, &key, |(key, _)| *key),
+                range.start
+            );
+            assert_eq!(upper_bound_by_key(&array, &key, |(key, _)| *key), range.end);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::cmp::Ordering;
+
+use super::binary_search;
+use crate::sort::partial_compare;
+
+pub fn exponential_search<T: PartialOrd>(array: &[T], target: &T) -> Option<usize> {
+    exponential_search_by(array, |element| partial_compare(element, target))
+}
+
+pub fn exponential_search_by_key<T, K: PartialOrd>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> Option<usize> {
+    exponential_search_by(array, |element| partial_compare(&f(element), key))
+}
+
+// Galloping search: doubles a bound from the front of the slice until it
+// passes the target, then binary searches the last step. Costs O(log i) for a
+// target at index `i`, which beats a plain binary search when matches tend to
+// be near the start. Returns the index of the first equal element, if any.
+pub fn exponential_search_by<T>(
+    array: &[T],
+    mut compare: impl FnMut(&T) -> Ordering,
+) -> Option<usize> {
+    let mut bound = 1;
+    while bound < array.len() && compare(&array[bound]).is_lt() {
+        bound *= 2;
+    }
+
+    let low = bound / 2;
+    let high = usize::min(bound + 1, array.len());
+    let index = low + binary_search::lower_bound_by(&array[low..high], &mut compare);
+
+    (index < array.len() && compare(&array[index]).is_eq()).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::sorter::registry;
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn agrees_with_binary_search_over_sorted_output() {
+        for (index, sorter) in registry::<u64>().iter().enumerate() {
+            let mut array = pseudo_random(1 + 61 * index, 100);
+            sorter.sort(array.as_mut_slice());
+
+            for target in 0..=101 {
+                assert_eq!(
+                    exponential_search(&array, &target),
+                    binary_search::binary_search(&array, &target),
+                    "{} {target}",
+                    sorter.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_empty_and_single_element_slices() {
+        let empty: [u64; 0] = [];
+
+        assert_eq!(exponential_search(&empty, &1), None);
+        assert_eq!(exponential_search(&[1], &1), Some(0));
+        assert_eq!(exponential_search(&[1], &2), None);
+    }
+
+    #[test]
+    fn search_by_comparator_and_key() {
+        let array: Vec<(u64, char)> = (0..1_000).map(|value| (value * 3, 'x')).collect();
+        let descending: Vec<u64> = (0..1_000).rev().collect();
+
+        assert_eq!(
+            exponential_search_by_key(&array, &2_997, |(key, _)| *key),
+            Some(999)
+        );
+        assert_eq!(
+            exponential_search_by_key(&array, &2_996, |(key, _)| *key),
+            None
+        );
+        assert_eq!(
+            exponential_search_by(&descending, |element| 10.cmp(element)),
+            Some(989)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::binary_search;
+use crate::sort::radix_sort::RadixKey;
+
+pub fn interpolation_search<T: RadixKey>(array: &[T], target: &T) -> Option<usize> {
+    search(array, target.radix_key(), |element| element.radix_key())
+}
+
+pub fn interpolation_search_by_key<T, K: RadixKey>(
+    array: &[T],
+    key: &K,
+    mut f: impl FnMut(&T) -> K,
+) -> Option<usize> {
+    search(array, key.radix_key(), |element| f(element).radix_key())
+}
+
+// Guesses the target's position from where its key falls between the keys at
+// either end of the range, so uniformly distributed keys are found in
+// O(log log n) probes on average; skewed keys can take up to O(n). There is no
+// comparator variant because the guess needs the distance between keys, not
+// just their order, which is what `RadixKey` provides. Returns the index of the
+// first equal element, if any.
+fn search<T>(array: &[T], target: u128, mut key: impl FnMut(&T) -> u128) -> Option<usize> {
+    if array.is_empty() {
+        return None;
+    }
+
+    let mut low = 0;
+    let mut high = array.len() - 1;
+
+    loop {
+        let low_key = key(&array[low]);
+        let high_key = key(&array[high]);
+        if target < low_key || target > high_key {
+            return None;
+        }
+
+        let position = if low_key == high_key {
+            low
+        } else {
+            let fraction = (target - low_key) as f64 / (high_key - low_key) as f64;
+            usize::min(low + ((high - low) as f64 * fraction) as usize, high)
+        };
+
+        // The checks above keep `position` strictly inside the range whenever
+        // the range needs to shrink, so neither update can step past the other
+        // end.
+        match key(&array[position]).cmp(&target) {
+            std::cmp::Ordering::Less => low = position + 1,
+            std::cmp::Ordering::Greater => high = position - 1,
+            std::cmp::Ordering::Equal => {
+                let first = binary_search::lower_bound_by(&array[low..=position], |element| {
+                    key(element).cmp(&target)
+                });
+
+                return Some(low + first);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::sorter::registry;
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn agrees_with_binary_search_over_sorted_output() {
+        for (index, sorter) in registry::<u64>().iter().enumerate() {
+            let mut array = pseudo_random(1 + 53 * index, 200);
+            sorter.sort(array.as_mut_slice());
+
+            for target in 0..=201 {
+                assert_eq!(
+                    interpolation_search(&array, &target),
+                    binary_search::binary_search(&array, &target),
+                    "{} {target}",
+                    sorter.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_skewed_and_extreme_keys() {
+        let array: Vec<u64> = (0..64)
+            .map(|shift| 1 << shift)
+            .chain([u64::MAX; 3])
+            .collect();
+
+        for (index, value) in array.iter().enumerate().take(64) {
+            assert_eq!(interpolation_search(&array, value), Some(index));
+        }
+        assert_eq!(interpolation_search(&array, &u64::MAX), Some(64));
+        assert_eq!(interpolation_search(&array, &3), None);
+        assert_eq!(interpolation_search(&[] as &[u64], &3), None);
+    }
+
+    #[test]
+    fn search_signed_keys_by_field() {
+        let mut array: Vec<(i32, &str)> = vec![(-50, "a"), (7, "b"), (-3, "c"), (7, "d"), (0, "e")];
+        crate::sort::merge_sort::sort_by_key(array.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(
+            interpolation_search_by_key(&array, &-3, |(key, _)| *key),
+            Some(1)
+        );
+        assert_eq!(
+            interpolation_search_by_key(&array, &7, |(key, _)| *key),
+            Some(3)
+        );
+        assert_eq!(
+            interpolation_search_by_key(&array, &1, |(key, _)| *key),
+            None
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod binary_search;
+pub mod exponential_search;
+pub mod interpolation_search;
+pub mod ternary_search;
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+
+use crate::sort::partial_compare;
+
+pub fn ternary_search<T: PartialOrd>(array: &[T]) -> Option<usize> {
+    ternary_search_by(array, partial_compare)
+}
+
+pub fn ternary_search_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> Option<usize> {
+    ternary_search_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Finds the peak of a slice that strictly increases and then strictly
+// decreases (either part may be empty) by discarding a third of the range per
+// step. With a reversed comparator it finds the valley instead. Plateaus make
+// the answer ambiguous, since equal probes cannot tell which side the peak is
+// on.
+pub fn ternary_search_by<T>(
+    array: &[T],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Option<usize> {
+    if array.is_empty() {
+        return None;
+    }
+
+    let mut low = 0;
+    let mut high = array.len() - 1;
+
+    while high - low > 2 {
+        let third = (high - low) / 3;
+        let left = low + third;
+        let right = high - third;
+
+        if compare(&array[left], &array[right]).is_lt() {
+            low = left + 1;
+        } else {
+            high = right;
+        }
+    }
+
+    let mut peak = low;
+    for index in low + 1..=high {
+        if compare(&array[peak], &array[index]).is_lt() {
+            peak = index;
+        }
+    }
+
+    Some(peak)
+}
+
+// Finds where a unimodal function peaks on `[low, high]`, narrowing the range
+// until it is no wider than `tolerance`, or until the range is so few floats
+// wide that a third of it rounds away and it stops shrinking.
+pub fn ternary_search_continuous(
+    mut low: f64,
+    mut high: f64,
+    tolerance: f64,
+    mut f: impl FnMut(f64) -> f64,
+) -> f64 {
+    assert!(
+        tolerance > 0.0,
+        "tolerance must be positive, got {tolerance}"
+    );
+
+    while high - low > tolerance {
+        let left = low + (high - low) / 3.0;
+        let right = high - (high - low) / 3.0;
+        if left == low || right == high {
+            break;
+        }
+
+        if f(left) < f(right) {
+            low = left;
+        } else {
+            high = right;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Builds a strictly unimodal slice by sorting distinct values with the
+    // crate's sorts: an ascending run followed by a descending one.
+    fn mountain(ascending: usize, descending: usize) -> Vec<u64> {
+        let mut values: Vec<u64> = pseudo_random(ascending + descending, u64::MAX);
+        crate::sort::sort(values.as_mut_slice());
+        values.dedup();
+
+        let mut left: Vec<u64> = values.iter().copied().step_by(2).collect();
+        let mut right: Vec<u64> = values.iter().copied().skip(1).step_by(2).collect();
+        left.truncate(ascending);
+        right.truncate(descending);
+        crate::sort::sort_by(right.as_mut_slice(), |a, b| b.cmp(a));
+
+        left.into_iter().chain(right).collect()
+    }
+
+    #[test]
+    fn finds_peak_of_every_shape() {
+        for ascending in 0..30 {
+            for descending in 0..30 {
+                let array = mountain(ascending, descending);
+                let expected = (0..array.len()).max_by_key(|index| array[*index]);
+
+                assert_eq!(ternary_search(&array), expected, "{ascending} {descending}");
+            }
+        }
+    }
+
+    #[test]
+    fn finds_valley_with_reversed_comparator() {
+        let array: Vec<i64> = (-500..=300).map(|value: i64| value.abs()).collect();
+
+        assert_eq!(ternary_search_by(&array, |a, b| b.cmp(a)), Some(500));
+        assert_eq!(ternary_search_by_key(&array, |value| -value), Some(500));
+    }
+
+    #[test]
+    fn finds_maximum_of_continuous_function() {
+        let peak = ternary_search_continuous(-10.0, 10.0, 1e-9, |x| -(x - 1.5) * (x - 1.5));
+
+        assert!((peak - 1.5).abs() < 1e-6);
+    }
+
+    // Floats near 1e16 are 2.0 apart, so the range is two steps wide and a third
+    // of it rounds away before it gets down to the tolerance.
+    #[test]
+    fn stops_when_the_range_cannot_shrink() {
+        let peak = ternary_search_continuous(1e16, 1e16 + 4.0, 1.0, |x| -(x - 1e16 - 2.0).abs());
+
+        assert!((1e16..=1e16 + 4.0).contains(&peak));
+    }
+}
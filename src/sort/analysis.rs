@@ -0,0 +1,381 @@
+use std::cmp::Ordering;
+
+use super::{partial_compare, patience};
+
+pub fn is_sorted<T: PartialOrd>(array: &[T]) -> bool {
+    is_sorted_by(array, partial_compare)
+}
+
+pub fn is_sorted_by_key<T, K: PartialOrd>(array: &[T], mut key: impl FnMut(&T) -> K) -> bool {
+    is_sorted_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Equal neighbours are allowed, so this is the postcondition every sort in the
+// crate guarantees.
+pub fn is_sorted_by<T>(array: &[T], mut compare: impl FnMut(&T, &T) -> Ordering) -> bool {
+    array
+        .windows(2)
+        .all(|pair| !compare(&pair[0], &pair[1]).is_gt())
+}
+
+pub fn count_inversions<T: PartialOrd>(array: &[T]) -> usize {
+    count_inversions_by(array, partial_compare)
+}
+
+pub fn count_inversions_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> usize {
+    count_inversions_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Counts the pairs of positions `i < j` whose elements are strictly out of
+// order. Merge sorts a list of indices rather than the slice itself, so the
+// input is left untouched and needs no `Clone`.
+pub fn count_inversions_by<T>(array: &[T], mut compare: impl FnMut(&T, &T) -> Ordering) -> usize {
+    let mut order: Vec<usize> = (0..array.len()).collect();
+    let mut buffer = Vec::with_capacity(array.len() / 2);
+
+    split_and_count(&mut order, &mut buffer, &mut |a: &usize, b: &usize| {
+        compare(&array[*a], &array[*b])
+    })
+}
+
+fn split_and_count(
+    order: &mut [usize],
+    buffer: &mut Vec<usize>,
+    compare: &mut impl FnMut(&usize, &usize) -> Ordering,
+) -> usize {
+    if order.len() < 2 {
+        return 0;
+    }
+
+    let middle = order.len() / 2;
+    let left = split_and_count(&mut order[..middle], buffer, compare);
+    let right = split_and_count(&mut order[middle..], buffer, compare);
+
+    left + right + merge_and_count(order, middle, buffer, compare)
+}
+
+// Each element taken from the right run jumps ahead of everything still left in
+// the left run, and each of those pairs is one inversion.
+fn merge_and_count(
+    order: &mut [usize],
+    middle: usize,
+    buffer: &mut Vec<usize>,
+    compare: &mut impl FnMut(&usize, &usize) -> Ordering,
+) -> usize {
+    if !compare(&order[middle - 1], &order[middle]).is_gt() {
+        return 0;
+    }
+
+    buffer.clear();
+    buffer.extend_from_slice(&order[..middle]);
+
+    let mut inversions = 0;
+    let mut left = 0;
+    let mut right = middle;
+    let mut write = 0;
+
+    while left < buffer.len() && right < order.len() {
+        if compare(&order[right], &buffer[left]).is_lt() {
+            order[write] = order[right];
+            inversions += buffer.len() - left;
+            right += 1;
+        } else {
+            order[write] = buffer[left];
+            left += 1;
+        }
+
+        write += 1;
+    }
+
+    order[write..write + buffer.len() - left].copy_from_slice(&buffer[left..]);
+    inversions
+}
+
+pub fn count_runs<T: PartialOrd>(array: &[T]) -> usize {
+    count_runs_by(array, partial_compare)
+}
+
+pub fn count_runs_by_key<T, K: PartialOrd>(array: &[T], mut key: impl FnMut(&T) -> K) -> usize {
+    count_runs_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Counts the maximal non-descending runs the slice splits into, which is one
+// for a sorted slice and zero for an empty one. Descending stretches count one
+// run per element.
+pub fn count_runs_by<T>(array: &[T], mut compare: impl FnMut(&T, &T) -> Ordering) -> usize {
+    if array.is_empty() {
+        return 0;
+    }
+
+    1 + array
+        .windows(2)
+        .filter(|pair| compare(&pair[0], &pair[1]).is_gt())
+        .count()
+}
+
+pub fn longest_non_decreasing_subsequence_length<T: PartialOrd>(array: &[T]) -> usize {
+    longest_non_decreasing_subsequence_length_by(array, partial_compare)
+}
+
+pub fn longest_non_decreasing_subsequence_length_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> usize {
+    longest_non_decreasing_subsequence_length_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Equal elements may follow each other, so a sorted slice scores its full
+// length even with duplicates. `patience` reconstructs the subsequence itself.
+pub fn longest_non_decreasing_subsequence_length_by<T>(
+    array: &[T],
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> usize {
+    patience::longest_subsequence(array, false, compare).len()
+}
+
+// How far a slice is from sorted, by three standard measures: how many pairs
+// are out of order, how many sorted runs it falls into, and how many elements
+// are already in sorted position relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Presortedness {
+    pub length: usize,
+    pub inversions: usize,
+    pub runs: usize,
+    pub longest_non_decreasing_subsequence: usize,
+}
+
+impl Presortedness {
+    pub fn is_sorted(&self) -> bool {
+        self.inversions == 0
+    }
+
+    pub fn max_inversions(&self) -> usize {
+        self.length * self.length.saturating_sub(1) / 2
+    }
+
+    // The fewest elements that would have to be taken out, and reinserted, to
+    // leave the rest sorted.
+    pub fn removals(&self) -> usize {
+        self.length - self.longest_non_decreasing_subsequence
+    }
+
+    // Insertion sort does one comparison per element plus one per inversion,
+    // so it beats an O(n log n) sort for as long as the inversions stay within
+    // about `n log2 n`. Tiny slices always qualify.
+    pub fn suits_insertion_sort(&self) -> bool {
+        let log = (usize::BITS - self.length.leading_zeros()) as usize;
+        self.inversions <= self.length * log
+    }
+}
+
+pub fn presortedness<T: PartialOrd>(array: &[T]) -> Presortedness {
+    presortedness_by(array, partial_compare)
+}
+
+pub fn presortedness_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> Presortedness {
+    presortedness_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+pub fn presortedness_by<T>(
+    array: &[T],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Presortedness {
+    Presortedness {
+        length: array.len(),
+        inversions: count_inversions_by(array, &mut compare),
+        runs: count_runs_by(array, &mut compare),
+        longest_non_decreasing_subsequence: longest_non_decreasing_subsequence_length_by(
+            array,
+            &mut compare,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{insertion_sort::InsertionSort, sorter::registry, stats};
+    use crate::testing::pseudo_random;
+
+    fn brute_force_inversions(array: &[u64]) -> usize {
+        (0..array.len())
+            .map(|i| array[i + 1..].iter().filter(|b| array[i] > **b).count())
+            .sum()
+    }
+
+    fn brute_force_longest_non_decreasing_subsequence(array: &[u64]) -> usize {
+        let mut lengths = vec![1; array.len()];
+        for j in 0..array.len() {
+            for i in 0..j {
+                if array[i] <= array[j] {
+                    lengths[j] = usize::max(lengths[j], lengths[i] + 1);
+                }
+            }
+        }
+
+        lengths.into_iter().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn every_sorter_output_is_sorted() {
+        for sorter in registry::<u64>() {
+            let mut array = pseudo_random(1_000, 100);
+            assert!(!is_sorted(&array));
+
+            sorter.sort(array.as_mut_slice());
+
+            assert!(is_sorted(&array), "{}", sorter.name());
+            assert!(!is_sorted_by(&array, |a, b| b.cmp(a)), "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn is_sorted_accepts_short_and_equal_slices() {
+        assert!(is_sorted::<u64>(&[]));
+        assert!(is_sorted(&[1]));
+        assert!(is_sorted(&[2, 2, 2]));
+        assert!(!is_sorted(&[1, 3, 2]));
+        assert!(is_sorted_by_key(&[(3, 'a'), (1, 'b')], |(_, c)| *c));
+    }
+
+    #[test]
+    fn count_inversions_matches_brute_force() {
+        for length in [0, 1, 2, 3, 10, 33, 200] {
+            for modulus in [2, 10, 1_000] {
+                let array = pseudo_random(length, modulus);
+
+                assert_eq!(
+                    count_inversions(&array),
+                    brute_force_inversions(&array),
+                    "{length} {modulus}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn count_inversions_of_sorted_and_reversed() {
+        let sorted: Vec<u64> = (0..1_000).collect();
+        let reversed: Vec<u64> = (0..1_000).rev().collect();
+
+        assert_eq!(count_inversions(&sorted), 0);
+        assert_eq!(count_inversions(&reversed), 1_000 * 999 / 2);
+        assert_eq!(count_inversions(&[5; 100]), 0);
+        assert_eq!(
+            count_inversions_by(&sorted, |a, b| b.cmp(a)),
+            1_000 * 999 / 2
+        );
+        assert_eq!(count_inversions_by_key(&reversed, |value| !value), 0);
+    }
+
+    #[test]
+    fn insertion_sort_swaps_once_per_inversion() {
+        let mut array = pseudo_random(500, 50);
+        let inversions = count_inversions(&array);
+
+        let stats = stats::measure(&InsertionSort, array.as_mut_slice());
+
+        assert_eq!(stats.swaps, inversions);
+    }
+
+    #[test]
+    fn count_runs_splits_at_descents() {
+        assert_eq!(count_runs::<u64>(&[]), 0);
+        assert_eq!(count_runs(&[7]), 1);
+        assert_eq!(count_runs(&[1, 2, 2, 3]), 1);
+        assert_eq!(count_runs(&[1, 5, 2, 6, 3, 3, 7]), 3);
+        assert_eq!(count_runs(&[4, 3, 2, 1]), 4);
+        assert_eq!(count_runs_by(&[4, 3, 2, 1], |a, b| b.cmp(a)), 1);
+        assert_eq!(count_runs_by_key(&["ccc", "a", "bb"], |word| word.len()), 2);
+    }
+
+    #[test]
+    fn longest_non_decreasing_subsequence_length_matches_brute_force() {
+        for length in [0, 1, 2, 10, 57, 300] {
+            for modulus in [3, 1_000] {
+                let array = pseudo_random(length, modulus);
+
+                assert_eq!(
+                    longest_non_decreasing_subsequence_length(&array),
+                    brute_force_longest_non_decreasing_subsequence(&array),
+                    "{length} {modulus}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn longest_non_decreasing_subsequence_length_by_comparator_and_key() {
+        let array = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+
+        assert_eq!(longest_non_decreasing_subsequence_length(&array), 5);
+        assert_eq!(
+            longest_non_decreasing_subsequence_length_by(&array, |a, b| b.cmp(a)),
+            4
+        );
+        assert_eq!(
+            longest_non_decreasing_subsequence_length_by_key(&["bb", "a", "cc", "ddd"], |word| {
+                word.len()
+            }),
+            3
+        );
+    }
+
+    #[test]
+    fn longest_non_decreasing_subsequence_length_counts_duplicates() {
+        assert_eq!(longest_non_decreasing_subsequence_length(&[1, 1, 1]), 3);
+        assert_eq!(
+            longest_non_decreasing_subsequence_length(&[2, 2, 1, 1, 1]),
+            3
+        );
+        assert_eq!(
+            longest_non_decreasing_subsequence_length(&[3, 1, 3, 1, 3]),
+            3
+        );
+        assert_eq!(
+            longest_non_decreasing_subsequence_length(&pseudo_random(500, 1)),
+            500
+        );
+    }
+
+    #[test]
+    fn presortedness_reports_every_measure() {
+        let report = presortedness(&[2, 1, 3, 5, 4]);
+
+        assert_eq!(
+            report,
+            Presortedness {
+                length: 5,
+                inversions: 2,
+                runs: 3,
+                longest_non_decreasing_subsequence: 3,
+            }
+        );
+        assert_eq!(report.max_inversions(), 10);
+        assert_eq!(report.removals(), 2);
+        assert!(!report.is_sorted());
+        assert!(presortedness::<u64>(&[]).is_sorted());
+        assert!(presortedness_by_key(&[5, 4, 3], |value| -value).is_sorted());
+    }
+
+    #[test]
+    fn suits_insertion_sort_only_when_nearly_sorted() {
+        let mut nearly_sorted: Vec<u64> = (0..10_000).collect();
+        for i in (0..10_000).step_by(100) {
+            nearly_sorted.swap(i, i + 1);
+        }
+        let random = pseudo_random(10_000, 10_000);
+        let reversed: Vec<u64> = (0..10_000).rev().collect();
+
+        assert!(presortedness(&nearly_sorted).suits_insertion_sort());
+        assert!(!presortedness(&random).suits_insertion_sort());
+        assert!(!presortedness(&reversed).suits_insertion_sort());
+        assert!(presortedness(&[3, 2, 1]).suits_insertion_sort());
+    }
+}
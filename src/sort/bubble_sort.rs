@@ -1,9 +1,29 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
 pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn sort_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
     for i in (0..array.len()).rev() {
         let mut no_swap = true;
         for j in 0..i {
-            if array[j] > array[j + 1] {
-                array.swap(j, j + 1);
+            if ops.compare(&array[j], &array[j + 1]).is_gt() {
+                ops.swap(array, j, j + 1);
                 no_swap = false;
             }
         }
@@ -14,6 +34,44 @@ pub fn sort<T: PartialOrd>(array: &mut [T]) {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BubbleSort;
+
+impl<T> Sorter<T> for BubbleSort {
+    fn name(&self) -> &'static str {
+        "bubble_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sort::bubble_sort;
@@ -50,4 +108,26 @@ mod tests {
 
         assert_eq!(array_1, array_2);
     }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        bubble_sort::sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (2, "b"), (1, "d")];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        bubble_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
 }
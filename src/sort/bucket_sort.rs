@@ -0,0 +1,428 @@
+use std::cmp::Ordering;
+
+use super::{
+    insertion_sort, intro_sort, partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    radix_sort::{apply_permutation, apply_permutation_by},
+    random::XorShift,
+    sorter::{Complexity, Sorter},
+};
+use crate::search::binary_search;
+
+const DEFAULT_SAMPLE_SORT_BUCKETS: usize = 16;
+const OVERSAMPLING: usize = 8;
+const SAMPLE_SORT_INSERTION_THRESHOLD: usize = 32;
+
+// Places a key on the number line so that keys can be spread across buckets
+// by value. The position must never decrease as the key increases; `as f64`
+// guarantees that for every primitive number, even where it rounds.
+pub trait BucketKey: Copy + PartialOrd {
+    fn position(&self) -> f64;
+}
+
+macro_rules! impl_bucket_key {
+    ($($number:ty),*) => {
+        $(
+            impl BucketKey for $number {
+                fn position(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_bucket_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// Uses one bucket per element, so that keys spread evenly between the
+// smallest and largest leave about one element in each bucket.
+pub fn sort<K: BucketKey>(array: &mut [K]) {
+    sort_with_buckets(array, array.len().max(1));
+}
+
+pub fn sort_by_key<T, K: BucketKey>(array: &mut [T], key: impl FnMut(&T) -> K) {
+    sort_by_key_with_buckets(array, array.len().max(1), key);
+}
+
+pub fn sort_with_buckets<K: BucketKey>(array: &mut [K], buckets: usize) {
+    sort_by_key_with_buckets(array, buckets, |value| *value);
+}
+
+// Splits the range between the smallest and largest key into `buckets` equal
+// intervals, moves every element into its interval's bucket and insertion
+// sorts each bucket. Uniform keys take linear time; keys crowded into a few
+// buckets degrade towards insertion sort but still come out sorted, as the
+// buckets only need to be in the right order relative to each other. Filling
+// the buckets keeps elements in their original order, so the sort is stable.
+pub fn sort_by_key_with_buckets<T, K: BucketKey>(
+    array: &mut [T],
+    buckets: usize,
+    mut key: impl FnMut(&T) -> K,
+) {
+    assert!(buckets > 0, "bucket sort needs at least one bucket");
+
+    let positions: Vec<f64> = array
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let position = key(value).position();
+            assert!(
+                !position.is_nan(),
+                "cannot bucket a NaN key, found at index {index}"
+            );
+            position
+        })
+        .collect();
+
+    let (min, max) = positions.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), position| (min.min(*position), max.max(*position)),
+    );
+
+    // When every key sits at one position the scale is infinite, and when the
+    // range overflows it is zero. Either way the products below are zero or
+    // NaN, which the cast turns into 0, so everything shares the first bucket.
+    let scale = buckets as f64 / (max - min);
+    let bucket_of = |position: f64| (((position - min) * scale) as usize).min(buckets - 1);
+
+    let mut starts = vec![0; buckets + 1];
+    for position in &positions {
+        starts[bucket_of(*position) + 1] += 1;
+    }
+    for index in 1..starts.len() {
+        starts[index] += starts[index - 1];
+    }
+
+    let mut next = starts.clone();
+    let mut order = vec![0; positions.len()];
+    for (index, position) in positions.iter().enumerate() {
+        let bucket = bucket_of(*position);
+        order[next[bucket]] = index;
+        next[bucket] += 1;
+    }
+
+    apply_permutation(array, &mut order);
+
+    for bucket in starts.windows(2) {
+        insertion_sort::sort_by(&mut array[bucket[0]..bucket[1]], |a, b| {
+            partial_compare(&key(a), &key(b))
+        });
+    }
+}
+
+pub fn sample_sort<T: PartialOrd>(array: &mut [T]) {
+    sample_sort_by(array, partial_compare);
+}
+
+pub fn sample_sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sample_sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sample_sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sample_sort_with_by(array, DEFAULT_SAMPLE_SORT_BUCKETS, compare);
+}
+
+pub fn sample_sort_with<T: PartialOrd>(array: &mut [T], buckets: usize) {
+    sample_sort_with_by(array, buckets, partial_compare);
+}
+
+pub fn sample_sort_with_by<T>(
+    array: &mut [T],
+    buckets: usize,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    let mut rng = XorShift::new();
+    sample_sort_with_ops(array, buckets, &mut rng, &mut Probed::new(compare, NoProbe));
+}
+
+// A bucket sort for keys with no known distribution: the bucket boundaries
+// are read off a sorted random sample instead of being spaced evenly, so each
+// bucket gets a similar share of the elements whatever the keys look like.
+// Elements equal to a splitter get a bucket of their own, which needs no
+// further sorting. That bucket always holds the splitter itself, so every
+// other bucket is smaller than the slice and the recursion finishes even when
+// the keys are mostly duplicates.
+pub(crate) fn sample_sort_with_ops<T>(
+    array: &mut [T],
+    buckets: usize,
+    rng: &mut XorShift,
+    ops: &mut impl SortOps<T>,
+) {
+    assert!(buckets > 1, "sample sort needs at least two buckets");
+
+    if array.len() <= SAMPLE_SORT_INSERTION_THRESHOLD {
+        insertion_sort::sort_with_ops(array, ops);
+        return;
+    }
+
+    let mut sample: Vec<usize> = (0..usize::min((buckets - 1) * OVERSAMPLING, array.len()))
+        .map(|_| rng.below(array.len()))
+        .collect();
+    intro_sort::sort_by(&mut sample, |a, b| ops.compare(&array[*a], &array[*b]));
+
+    let mut splitters: Vec<usize> = (1..buckets)
+        .map(|bucket| sample[bucket * sample.len() / buckets])
+        .collect();
+    splitters.dedup_by(|a, b| ops.compare(&array[*a], &array[*b]).is_eq());
+
+    // Bucket `2 * i` holds the elements between splitters `i - 1` and `i`, and
+    // bucket `2 * i + 1` the elements equal to splitter `i`.
+    let mut starts = vec![0; 2 * splitters.len() + 2];
+    let classes: Vec<usize> = (0..array.len())
+        .map(|index| {
+            let splitter = binary_search::lower_bound_by(&splitters, |splitter| {
+                ops.compare(&array[*splitter], &array[index])
+            });
+            let is_equal = splitters
+                .get(splitter)
+                .is_some_and(|splitter| ops.compare(&array[*splitter], &array[index]).is_eq());
+
+            let class = 2 * splitter + usize::from(is_equal);
+            starts[class + 1] += 1;
+            class
+        })
+        .collect();
+    for index in 1..starts.len() {
+        starts[index] += starts[index - 1];
+    }
+
+    let mut next = starts.clone();
+    let mut order = vec![0; array.len()];
+    for (index, class) in classes.into_iter().enumerate() {
+        order[next[class]] = index;
+        next[class] += 1;
+    }
+
+    apply_permutation_by(array, &mut order, |array, i, j| ops.swap(array, i, j));
+
+    for bucket in starts.windows(2).step_by(2) {
+        sample_sort_with_ops(&mut array[bucket[0]..bucket[1]], buckets, rng, ops);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SampleSort {
+    pub buckets: usize,
+}
+
+impl Default for SampleSort {
+    fn default() -> Self {
+        Self {
+            buckets: DEFAULT_SAMPLE_SORT_BUCKETS,
+        }
+    }
+}
+
+impl<T> Sorter<T> for SampleSort {
+    fn name(&self) -> &'static str {
+        "sample_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        false
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sample_sort_with_by(array, self.buckets, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        let mut rng = XorShift::new();
+        sample_sort_with_ops(
+            array,
+            self.buckets,
+            &mut rng,
+            &mut Probed::new(compare, probe),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bucket_sort;
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn sort_uniform_floats() {
+        let mut array_1: Vec<f64> = pseudo_random(5_000, 1 << 40)
+            .into_iter()
+            .map(|value| value as f64 / (1u64 << 40) as f64 - 0.5)
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(f64::total_cmp);
+        bucket_sort::sort(array_1.as_mut_slice());
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_extreme_keys() {
+        let mut floats = vec![
+            1.5,
+            f64::INFINITY,
+            -0.25,
+            f64::MAX,
+            f64::NEG_INFINITY,
+            -f64::MAX,
+            0.0,
+        ];
+        let mut expected = floats.clone();
+
+        expected.sort_by(f64::total_cmp);
+        bucket_sort::sort(floats.as_mut_slice());
+
+        assert_eq!(floats, expected);
+
+        // These are too close together to tell apart as `f64`s, so they all
+        // share a bucket and are ordered by insertion sort.
+        let mut integers: Vec<u64> = pseudo_random(100, 1_000)
+            .into_iter()
+            .map(|value| u64::MAX - value)
+            .collect();
+        let mut expected = integers.clone();
+
+        expected.sort();
+        bucket_sort::sort(integers.as_mut_slice());
+
+        assert_eq!(integers, expected);
+
+        let mut signed = vec![i128::MAX, -3, i128::MIN, 0, 7, i128::MIN + 1];
+        let mut expected = signed.clone();
+
+        expected.sort();
+        bucket_sort::sort(signed.as_mut_slice());
+
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn sort_with_any_number_of_buckets() {
+        let input: Vec<i64> = pseudo_random(1_000, 200)
+            .into_iter()
+            .map(|value| value as i64 - 100)
+            .collect();
+        let mut expected = input.clone();
+        expected.sort();
+
+        for buckets in [1, 2, 7, 1_000, 10_000] {
+            let mut array = input.clone();
+
+            bucket_sort::sort_with_buckets(array.as_mut_slice(), buckets);
+
+            assert_eq!(array, expected, "{buckets} buckets");
+        }
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut array_1: Vec<(f32, usize)> = pseudo_random(2_000, 50)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key as f32 / 10.0, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| a.0.total_cmp(&b.0));
+        bucket_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot bucket a NaN key, found at index 2")]
+    fn sort_rejects_nan() {
+        bucket_sort::sort(&mut [1.0, 2.0, f64::NAN, 0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bucket sort needs at least one bucket")]
+    fn sort_rejects_zero_buckets() {
+        bucket_sort::sort_with_buckets(&mut [3, 1, 2], 0);
+    }
+
+    #[test]
+    fn sample_sort_sorts() {
+        for length in [0, 1, 31, 33, 1_000, 5_000] {
+            for modulus in [1, 3, 100, u64::MAX] {
+                let mut array_1 = pseudo_random(length, modulus);
+                let mut array_2 = array_1.clone();
+
+                array_2.sort();
+                bucket_sort::sample_sort(array_1.as_mut_slice());
+
+                assert_eq!(array_1, array_2, "{length} elements modulo {modulus}");
+            }
+        }
+    }
+
+    #[test]
+    fn sample_sort_presorted_inputs() {
+        let mut ascending: Vec<u32> = (0..3_000).collect();
+        let mut descending: Vec<u32> = (0..3_000).rev().collect();
+
+        bucket_sort::sample_sort(ascending.as_mut_slice());
+        bucket_sort::sample_sort(descending.as_mut_slice());
+
+        assert!(ascending.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(ascending, descending);
+    }
+
+    #[test]
+    fn sample_sort_by_descending_order() {
+        let mut array_1 = pseudo_random(1_000, 300);
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        bucket_sort::sample_sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sample_sort_by_key_is_stable() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(3_000, 40)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        bucket_sort::sample_sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sample_sort_with_any_number_of_buckets() {
+        let input = pseudo_random(2_000, 500);
+        let mut expected = input.clone();
+        expected.sort();
+
+        for buckets in [2, 3, 64, 2_000] {
+            let mut array = input.clone();
+
+            bucket_sort::sample_sort_with(array.as_mut_slice(), buckets);
+
+            assert_eq!(array, expected, "{buckets} buckets");
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use std::{
+    cmp::Ordering,
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+};
+
+use crate::testing::pseudo_random;
+
+// Test support shared by every sort: the input shapes that tend to trip sorts
+// up, and checks that take the sort as a closure, so free functions outside
+// the registry can be run through them as easily as `Sorter`s.
+
+// Around the sizes where sorts switch strategy (insertion thresholds, minimum
+// run lengths, bucket counts) as well as a few larger ones.
+const LENGTHS: [usize; 20] = [
+    0, 1, 2, 3, 4, 5, 7, 8, 15, 16, 17, 31, 32, 33, 64, 65, 100, 257, 1_000, 2_049,
+];
+
+const PANIC_LENGTHS: [usize; 3] = [2, 20, 150];
+
+// How many different comparison counts to give up after, per input.
+const PANIC_POINTS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Shape {
+    Random,
+    FewDistinct,
+    Sorted,
+    Reversed,
+    AllEqual,
+    // Five ascending runs, whose lengths differ by at most one.
+    Sawtooth,
+    // Ascending to the middle, then descending.
+    OrganPipe,
+}
+
+impl Shape {
+    pub(crate) const ALL: [Shape; 7] = [
+        Shape::Random,
+        Shape::FewDistinct,
+        Shape::Sorted,
+        Shape::Reversed,
+        Shape::AllEqual,
+        Shape::Sawtooth,
+        Shape::OrganPipe,
+    ];
+
+    pub(crate) fn generate(self, length: usize) -> Vec<u64> {
+        let length = length as u64;
+
+        match self {
+            Shape::Random => pseudo_random(length as usize, u64::MAX),
+            Shape::FewDistinct => pseudo_random(length as usize, 4),
+            Shape::Sorted => (0..length).collect(),
+            Shape::Reversed => (0..length).rev().collect(),
+            Shape::AllEqual => vec![7; length as usize],
+            // Element `i` is in run `i * 5 / length`, and each run counts up
+            // from the first index that lands in it.
+            Shape::Sawtooth => (0..length)
+                .map(|i| i - (i * 5 / length * length).div_ceil(5))
+                .collect(),
+            Shape::OrganPipe => (0..length).map(|i| u64::min(i, length - 1 - i)).collect(),
+        }
+    }
+}
+
+// Orders by `key` alone, for checking the stability of sorts that take their
+// order from `PartialOrd`: `index` records where each element started.
+#[derive(Debug, Clone)]
+pub(crate) struct Keyed {
+    pub(crate) key: u64,
+    pub(crate) index: usize,
+}
+
+impl Keyed {
+    pub(crate) fn tag(keys: impl IntoIterator<Item = u64>) -> Vec<Keyed> {
+        keys.into_iter()
+            .enumerate()
+            .map(|(index, key)| Keyed { key, index })
+            .collect()
+    }
+}
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+pub(crate) fn check_sorts(name: &str, sort: impl FnMut(&mut [u64])) {
+    check_sorts_below(name, u64::MAX, sort);
+}
+
+// For sorts that only handle a limited range of values: every shape is
+// generated as usual and then reduced modulo `bound`.
+pub(crate) fn check_sorts_below(name: &str, bound: u64, mut sort: impl FnMut(&mut [u64])) {
+    for shape in Shape::ALL {
+        for length in LENGTHS {
+            let input: Vec<u64> = shape
+                .generate(length)
+                .into_iter()
+                .map(|value| value % bound)
+                .collect();
+            let mut expected = input.clone();
+            expected.sort();
+
+            let mut actual = input;
+            sort(actual.as_mut_slice());
+
+            assert_eq!(actual, expected, "{name} on {length} {shape:?} elements");
+        }
+    }
+}
+
+// Tags every value with its position and expects the sort to order by the
+// value alone, so any equal values that trade places show up in the tags.
+pub(crate) fn check_stable(name: &str, mut sort_by_value: impl FnMut(&mut [(u64, usize)])) {
+    for shape in Shape::ALL {
+        for length in LENGTHS {
+            let input: Vec<(u64, usize)> = shape
+                .generate(length)
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (value, index))
+                .collect();
+            let mut expected = input.clone();
+            expected.sort_by_key(|(value, _)| *value);
+
+            let mut actual = input;
+            sort_by_value(actual.as_mut_slice());
+
+            assert_eq!(actual, expected, "{name} on {length} {shape:?} elements");
+        }
+    }
+}
+
+// Sorts with a comparator that panics once it has been called a given number
+// of times, for counts spread over everything a full sort needs, and checks
+// that the slice always still holds exactly the elements it started with. A
+// sort that moves elements through a buffer must put them back as it unwinds.
+// The panic is raised with `resume_unwind`, which skips the panic hook and so
+// keeps the test output quiet. The comparator counts atomically, so sorts that
+// share it between threads can be checked too.
+pub(crate) fn check_panic_safety(
+    name: &str,
+    sort_by: impl FnMut(&mut [u64], &(dyn Fn(&u64, &u64) -> Ordering + Sync)),
+) {
+    check_panic_safety_on(name, &PANIC_LENGTHS, sort_by);
+}
+
+pub(crate) fn check_panic_safety_on(
+    name: &str,
+    lengths: &[usize],
+    mut sort_by: impl FnMut(&mut [u64], &(dyn Fn(&u64, &u64) -> Ordering + Sync)),
+) {
+    for shape in [Shape::Random, Shape::FewDistinct, Shape::Reversed] {
+        for &length in lengths {
+            let input = shape.generate(length);
+            let mut expected = input.clone();
+            expected.sort();
+
+            let comparisons = AtomicUsize::new(0);
+            sort_by(input.clone().as_mut_slice(), &|a, b| {
+                comparisons.fetch_add(1, AtomicOrdering::Relaxed);
+                a.cmp(b)
+            });
+            let comparisons = comparisons.into_inner();
+
+            for limit in (0..comparisons).step_by(comparisons / PANIC_POINTS + 1) {
+                let mut actual = input.clone();
+                let remaining = AtomicUsize::new(limit);
+
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                    sort_by(actual.as_mut_slice(), &|a, b| {
+                        let counted = remaining.fetch_update(
+                            AtomicOrdering::Relaxed,
+                            AtomicOrdering::Relaxed,
+                            |remaining| remaining.checked_sub(1),
+                        );
+                        if counted.is_err() {
+                            panic::resume_unwind(Box::new("comparator gave up"));
+                        }
+                        a.cmp(b)
+                    })
+                }));
+
+                actual.sort();
+                assert_eq!(
+                    actual, expected,
+                    "{name} on {length} {shape:?} elements, panicking after {limit} comparisons"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::super::{
+        bucket_sort, counting_sort,
+        parallel::{self, Parallelism},
+        quick_sort::{self, Partition, Pivot},
+        radix_sort,
+        shell_sort::{self, GapSequence},
+        sorter,
+    };
+    use super::*;
+    use crate::data_structures::{
+        doubly_linked_list::DoublyLinkedList, linked_list::SinglyLinkedList,
+    };
+
+    // Enough threads to split the slice even where the machine has one core.
+    const PARALLELISM: Parallelism = Parallelism {
+        threads: 4,
+        cutoff: 32,
+    };
+
+    // Digit widths that do and do not divide the key width, up to the widest
+    // allowed.
+    const RADIX_BITS: [u32; 5] = [1, 3, 8, 11, 16];
+
+    // Past the length below which the parallel sorts never spawn a thread.
+    const PARALLEL_PANIC_LENGTHS: [usize; 2] = [150, 10_000];
+
+    // The linked lists are checked through a slice: it is copied into a list,
+    // sorted there and copied back, even when the sort panics, so the checks
+    // see whatever the list kept. Slots the list has no node left for get a
+    // value no input contains.
+    fn sort_singly_linked_list(
+        array: &mut [u64],
+        compare: &(dyn Fn(&u64, &u64) -> Ordering + Sync),
+    ) {
+        let mut list = SinglyLinkedList::from(array.to_vec());
+        let sorted = panic::catch_unwind(AssertUnwindSafe(|| list.sort_by(compare)));
+        copy_back(array, iter::from_fn(|| list.shift()));
+        if let Err(payload) = sorted {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    fn sort_doubly_linked_list(
+        array: &mut [u64],
+        compare: &(dyn Fn(&u64, &u64) -> Ordering + Sync),
+    ) {
+        let mut list = DoublyLinkedList::from(array.to_vec());
+        let sorted = panic::catch_unwind(AssertUnwindSafe(|| list.sort_by(compare)));
+        copy_back(array, list);
+        if let Err(payload) = sorted {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    fn copy_back(array: &mut [u64], mut values: impl Iterator<Item = u64>) {
+        for slot in array {
+            *slot = values.next().unwrap_or(u64::MAX);
+        }
+    }
+
+    #[test]
+    fn registered_sorters_match_slice_sort() {
+        for sorter in sorter::registry::<u64>() {
+            check_sorts(sorter.name(), |array| sorter.sort(array));
+        }
+    }
+
+    #[test]
+    fn registered_sorters_are_stable_where_claimed() {
+        for sorter in sorter::registry::<(u64, usize)>() {
+            if sorter.is_stable() {
+                check_stable(sorter.name(), |array| {
+                    sorter.sort_by(array, &mut |a, b| a.0.cmp(&b.0))
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn registered_sorters_survive_panicking_comparators() {
+        for sorter in sorter::registry::<u64>() {
+            check_panic_safety(sorter.name(), |array, compare| {
+                sorter.sort_by(array, &mut |a, b| compare(a, b))
+            });
+        }
+    }
+
+    #[test]
+    fn standalone_sorts_match_slice_sort() {
+        check_sorts("radix_sort::sort", radix_sort::sort);
+        check_sorts("radix_sort::sort_msd", radix_sort::sort_msd);
+        check_sorts("radix_sort::sort_msd_by_key", |array| {
+            radix_sort::sort_msd_by_key(array, |value| *value)
+        });
+        for radix_bits in RADIX_BITS {
+            check_sorts(&format!("radix_sort::sort_lsd {radix_bits}"), |array| {
+                radix_sort::sort_lsd(array, radix_bits)
+            });
+        }
+        check_sorts("bucket_sort::sort", bucket_sort::sort);
+        check_sorts_below("counting_sort::sort", 1_000, counting_sort::sort);
+        check_sorts("parallel::merge_sort", parallel::merge_sort);
+        check_sorts("parallel::quick_sort", parallel::quick_sort);
+        check_sorts("parallel::merge_sort_with", |array| {
+            parallel::merge_sort_with(array, PARALLELISM)
+        });
+        check_sorts("parallel::quick_sort_with", |array| {
+            parallel::quick_sort_with(array, PARALLELISM)
+        });
+
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                check_sorts(
+                    &format!("quick_sort::sort_with {partition:?} {pivot:?}"),
+                    |array| quick_sort::sort_with(array, partition, pivot),
+                );
+            }
+        }
+        for sequence in GapSequence::ALL {
+            check_sorts(
+                &format!("shell_sort::sort_with_sequence {sequence:?}"),
+                |array| shell_sort::sort_with_sequence(array, sequence),
+            );
+        }
+
+        check_sorts("bucket_sort::sample_sort", bucket_sort::sample_sort);
+        for buckets in [2, 3, 16, 256] {
+            check_sorts(
+                &format!("bucket_sort::sample_sort_with {buckets}"),
+                |array| bucket_sort::sample_sort_with(array, buckets),
+            );
+        }
+
+        check_sorts("SinglyLinkedList::sort_by", |array| {
+            sort_singly_linked_list(array, &u64::cmp)
+        });
+        check_sorts("DoublyLinkedList::sort_by", |array| {
+            sort_doubly_linked_list(array, &u64::cmp)
+        });
+    }
+
+    #[test]
+    fn standalone_sorts_survive_panicking_comparators() {
+        check_panic_safety_on(
+            "parallel::merge_sort_with_by",
+            &PARALLEL_PANIC_LENGTHS,
+            |array, compare| parallel::merge_sort_with_by(array, PARALLELISM, compare),
+        );
+        check_panic_safety_on(
+            "parallel::quick_sort_with_by",
+            &PARALLEL_PANIC_LENGTHS,
+            |array, compare| parallel::quick_sort_with_by(array, PARALLELISM, compare),
+        );
+        check_panic_safety("parallel::merge_sort_by", |array, compare| {
+            parallel::merge_sort_by(array, compare)
+        });
+        check_panic_safety("parallel::quick_sort_by", |array, compare| {
+            parallel::quick_sort_by(array, compare)
+        });
+
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                check_panic_safety(
+                    &format!("quick_sort::sort_with_by {partition:?} {pivot:?}"),
+                    |array, compare| quick_sort::sort_with_by(array, partition, pivot, compare),
+                );
+            }
+        }
+        for sequence in GapSequence::ALL {
+            check_panic_safety(
+                &format!("shell_sort::sort_with_sequence_by {sequence:?}"),
+                |array, compare| shell_sort::sort_with_sequence_by(array, sequence, compare),
+            );
+        }
+        for buckets in [2, 3, 16, 256] {
+            check_panic_safety(
+                &format!("bucket_sort::sample_sort_with_by {buckets}"),
+                |array, compare| bucket_sort::sample_sort_with_by(array, buckets, compare),
+            );
+        }
+
+        check_panic_safety("SinglyLinkedList::sort_by", sort_singly_linked_list);
+        check_panic_safety("DoublyLinkedList::sort_by", sort_doubly_linked_list);
+    }
+
+    #[test]
+    fn standalone_stable_sorts_are_stable() {
+        for radix_bits in RADIX_BITS {
+            check_stable(
+                &format!("radix_sort::sort_lsd_by_key {radix_bits}"),
+                |array| radix_sort::sort_lsd_by_key(array, radix_bits, |(value, _)| *value),
+            );
+        }
+        check_stable("bucket_sort::sort_by_key", |array| {
+            bucket_sort::sort_by_key(array, |(value, _)| *value)
+        });
+        check_stable("parallel::merge_sort_by_key", |array| {
+            parallel::merge_sort_by_key(array, |(value, _)| *value)
+        });
+    }
+
+    #[test]
+    fn shapes_have_the_requested_length() {
+        for shape in Shape::ALL {
+            for length in LENGTHS {
+                assert_eq!(shape.generate(length).len(), length, "{shape:?}");
+            }
+        }
+
+        assert_eq!(Shape::OrganPipe.generate(6), [0, 1, 2, 2, 1, 0]);
+        assert_eq!(
+            Shape::Sawtooth.generate(12),
+            [0, 1, 2, 0, 1, 0, 1, 2, 0, 1, 0, 1]
+        );
+    }
+}
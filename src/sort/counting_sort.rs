@@ -0,0 +1,94 @@
+use super::radix_sort::{apply_permutation, RadixKey};
+
+// Allocates one counter per value between the smallest and largest key, so it
+// is only a good fit when that range is small compared to the slice.
+pub fn sort<T: RadixKey>(array: &mut [T]) {
+    sort_by_key(array, |value| value.radix_key());
+}
+
+pub fn sort_by_key<T, K: RadixKey>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    let keys: Vec<u128> = array.iter().map(|value| key(value).radix_key()).collect();
+
+    let (Some(min), Some(max)) = (keys.iter().min(), keys.iter().max()) else {
+        return;
+    };
+
+    let range = usize::try_from(max - min)
+        .ok()
+        .and_then(|range| range.checked_add(1))
+        .expect("key range is too large for counting sort");
+
+    let mut starts = vec![0; range + 1];
+    for key in &keys {
+        starts[(key - min) as usize + 1] += 1;
+    }
+    for index in 1..starts.len() {
+        starts[index] += starts[index - 1];
+    }
+
+    let mut order = vec![0; keys.len()];
+    for (index, key) in keys.iter().enumerate() {
+        let bucket = (key - min) as usize;
+        order[starts[bucket]] = index;
+        starts[bucket] += 1;
+    }
+
+    apply_permutation(array, &mut order);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::counting_sort;
+
+    #[test]
+    fn sort_empty_array() {
+        let mut array: Vec<u32> = vec![];
+
+        counting_sort::sort(array.as_mut_slice());
+
+        assert!(array.is_empty());
+    }
+
+    #[test]
+    fn sort_negative_values_with_a_small_range() {
+        let mut array_1: Vec<i64> = vec![-3, 5, -50, -1, -99, 0, -3];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        counting_sort::sort(array_1.as_mut_slice());
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_extreme_values_of_narrow_types() {
+        let mut array_1: Vec<i8> = vec![i8::MAX, 0, -1, i8::MIN, 1, i8::MIN, i8::MAX];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        counting_sort::sort(array_1.as_mut_slice());
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut array: Vec<(u8, usize)> = (0..1_000)
+            .map(|index| ((index * 7 % 13) as u8, index))
+            .collect();
+        let mut expected = array.clone();
+        expected.sort_by_key(|(key, _)| *key);
+
+        counting_sort::sort_by_key(array.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "key range is too large for counting sort")]
+    fn sort_panics_when_range_does_not_fit() {
+        let mut array = vec![0u128, u128::MAX];
+
+        counting_sort::sort(array.as_mut_slice());
+    }
+}
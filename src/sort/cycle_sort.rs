@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+const HELD: &str = "the hand holds an element until its cycle closes";
+
+pub fn sort<T: PartialOrd + Clone>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T: Clone, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T: Clone>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+// Follows each cycle of the permutation that sorts the slice, counting the
+// smaller elements to find where the element in hand belongs and writing it
+// straight there. Elements already in place are never written and the rest
+// are written exactly once, which is the fewest writes any sort can make;
+// selection sort needs up to two per element, one for each side of a swap.
+pub(crate) fn sort_with_ops<T: Clone>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    for start in 0..array.len().saturating_sub(1) {
+        let mut position = rank(array, start, &array[start], ops);
+        if position == start {
+            continue;
+        }
+
+        // The element in hand stands in for the temporary of a swap, so like
+        // that temporary it is not reported as scratch space.
+        let mut hand = Hand {
+            item: Some(array[start].clone()),
+            array: &mut *array,
+            start,
+        };
+        let item = hand.item.as_mut().expect(HELD);
+        while position != start {
+            while ops.compare(item, &hand.array[position]).is_eq() {
+                position += 1;
+            }
+
+            ops.replace(hand.array, position, item);
+            position = rank(hand.array, start, item, ops);
+        }
+
+        let item = hand.item.take().expect(HELD);
+        ops.write(hand.array, start, item);
+    }
+}
+
+// The element in hand while a cycle is followed. Until the cycle closes,
+// `array[start]` holds a stale copy of an element that has been written
+// elsewhere, so if the comparator panics, dropping the hand puts the element
+// it holds there instead of losing it.
+struct Hand<'a, T> {
+    array: &'a mut [T],
+    start: usize,
+    item: Option<T>,
+}
+
+impl<T> Drop for Hand<'_, T> {
+    fn drop(&mut self) {
+        if let Some(item) = self.item.take() {
+            self.array[self.start] = item;
+        }
+    }
+}
+
+// Where `item` goes among the unsorted elements from `start` on, ahead of any
+// elements equal to it.
+fn rank<T>(array: &[T], start: usize, item: &T, ops: &mut impl SortOps<T>) -> usize {
+    start
+        + array[start + 1..]
+            .iter()
+            .filter(|element| ops.is_less(element, item))
+            .count()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CycleSort;
+
+impl<T: Clone> Sorter<T> for CycleSort {
+    fn name(&self) -> &'static str {
+        "cycle_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{cycle_sort, selection_sort::SelectionSort, stats};
+    use super::CycleSort;
+    use crate::testing::pseudo_random;
+
+    fn misplaced(array: &[u64]) -> usize {
+        let mut sorted = array.to_vec();
+        sorted.sort();
+
+        array.iter().zip(&sorted).filter(|(a, b)| a != b).count()
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        cycle_sort::sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (2, "b"), (1, "a")];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        cycle_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn writes_only_misplaced_elements() {
+        let inputs = [
+            (0..300).collect(),
+            (0..300).rev().collect(),
+            pseudo_random(300, u64::MAX),
+            pseudo_random(300, 5),
+            vec![7; 50],
+        ];
+
+        for input in inputs {
+            let expected = misplaced(&input);
+            let mut array = input;
+
+            let stats = stats::measure(&CycleSort, array.as_mut_slice());
+
+            assert_eq!(stats.writes, expected);
+            assert_eq!(stats.swaps, 0);
+            assert_eq!(stats.auxiliary_memory, 0);
+            assert_eq!(misplaced(&array), 0);
+        }
+    }
+
+    #[test]
+    fn writes_less_than_selection_sort() {
+        for modulus in [u64::MAX, 50, 3] {
+            let input = pseudo_random(1_000, modulus);
+
+            let cycle = stats::measure(&CycleSort, input.clone().as_mut_slice());
+            let selection = stats::measure(&SelectionSort, input.clone().as_mut_slice());
+
+            assert!(cycle.writes < selection.writes, "{modulus}");
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use std::{
+    cmp::Ordering,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    iter,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+};
+
+use super::{merge, partial_compare, tim_sort};
+
+static NEXT_RUN: AtomicUsize = AtomicUsize::new(0);
+
+// Turns a byte stream into records and back. Records are written to temporary
+// run files with the same codec, so `read_record` must accept anything
+// `write_record` produces.
+pub trait Codec {
+    type Record;
+
+    // Returns `None` once the input is exhausted.
+    fn read_record(&self, reader: &mut dyn BufRead) -> io::Result<Option<Self::Record>>;
+
+    fn write_record(&self, writer: &mut dyn Write, record: &Self::Record) -> io::Result<()>;
+}
+
+// Newline-delimited UTF-8 text. A trailing `\r` is kept as part of the line,
+// and every line is written back with a `\n`, including the last.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lines;
+
+impl Codec for Lines {
+    type Record = String;
+
+    fn read_record(&self, reader: &mut dyn BufRead) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+        }
+
+        Ok(Some(line))
+    }
+
+    fn write_record(&self, writer: &mut dyn Write, record: &String) -> io::Result<()> {
+        writer.write_all(record.as_bytes())?;
+        writer.write_all(b"\n")
+    }
+}
+
+// Binary records of exactly `width` bytes each, with no separators.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedWidth {
+    pub width: usize,
+}
+
+impl Codec for FixedWidth {
+    type Record = Vec<u8>;
+
+    fn read_record(&self, reader: &mut dyn BufRead) -> io::Result<Option<Vec<u8>>> {
+        assert!(self.width > 0, "record width must be at least 1");
+
+        if reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+
+        let mut record = vec![0; self.width];
+        reader.read_exact(&mut record).map_err(|error| {
+            if error.kind() == io::ErrorKind::UnexpectedEof {
+                io::Error::new(error.kind(), "input ends with a partial record")
+            } else {
+                error
+            }
+        })?;
+
+        Ok(Some(record))
+    }
+
+    fn write_record(&self, writer: &mut dyn Write, record: &Vec<u8>) -> io::Result<()> {
+        writer.write_all(record)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    // The most records held in memory at once, which is also the length of
+    // each run spilled to disk.
+    pub chunk_size: usize,
+    // The most runs merged in one pass. With more runs than this, groups of
+    // runs are merged into longer runs first.
+    pub fan_in: usize,
+    pub temp_dir: PathBuf,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            chunk_size: 1 << 20,
+            fan_in: 64,
+            temp_dir: env::temp_dir(),
+        }
+    }
+}
+
+pub fn sort<C: Codec>(
+    input: impl Read,
+    output: impl Write,
+    codec: &C,
+    options: &Options,
+) -> io::Result<()>
+where
+    C::Record: PartialOrd + Clone,
+{
+    sort_by(input, output, codec, options, partial_compare)
+}
+
+pub fn sort_by_key<C: Codec, K: PartialOrd>(
+    input: impl Read,
+    output: impl Write,
+    codec: &C,
+    options: &Options,
+    mut key: impl FnMut(&C::Record) -> K,
+) -> io::Result<()>
+where
+    C::Record: Clone,
+{
+    sort_by(input, output, codec, options, |a, b| {
+        partial_compare(&key(a), &key(b))
+    })
+}
+
+// Stable: chunks are sorted with TimSort and ties between runs go to the run
+// that came first in the input.
+pub fn sort_by<C: Codec>(
+    input: impl Read,
+    output: impl Write,
+    codec: &C,
+    options: &Options,
+    mut compare: impl FnMut(&C::Record, &C::Record) -> Ordering,
+) -> io::Result<()>
+where
+    C::Record: Clone,
+{
+    assert!(options.chunk_size > 0, "chunk size must be at least 1");
+    assert!(options.fan_in > 1, "fan-in must be at least 2");
+
+    let mut reader = BufReader::new(input);
+    let mut writer = BufWriter::new(output);
+    let mut runs = Vec::new();
+
+    loop {
+        let mut chunk = Vec::new();
+        while chunk.len() < options.chunk_size {
+            match codec.read_record(&mut reader)? {
+                Some(record) => chunk.push(record),
+                None => break,
+            }
+        }
+
+        let exhausted = chunk.len() < options.chunk_size;
+        tim_sort::sort_by(&mut chunk, &mut compare);
+
+        // Everything fit in memory, so there is nothing to merge.
+        if runs.is_empty() && exhausted {
+            for record in &chunk {
+                codec.write_record(&mut writer, record)?;
+            }
+
+            return writer.flush();
+        }
+
+        if !chunk.is_empty() {
+            let (run, mut run_writer) = RunFile::create(&options.temp_dir)?;
+            for record in &chunk {
+                codec.write_record(&mut run_writer, record)?;
+            }
+            run_writer.flush()?;
+            runs.push(run);
+        }
+
+        if exhausted {
+            break;
+        }
+    }
+
+    while runs.len() > options.fan_in {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(options.fan_in));
+        for group in runs.chunks(options.fan_in) {
+            let (run, mut run_writer) = RunFile::create(&options.temp_dir)?;
+            merge(group, codec, &mut run_writer, &mut compare)?;
+            run_writer.flush()?;
+            merged.push(run);
+        }
+
+        runs = merged;
+    }
+
+    merge(&runs, codec, &mut writer, &mut compare)?;
+    writer.flush()
+}
+
+// K-way merge of the runs through `merge::merge_by`, which sends ties to the
+// earliest run. A read error is ordered before every record, so it comes out
+// of the merge as soon as it happens and stops the merge there.
+fn merge<C: Codec>(
+    runs: &[RunFile],
+    codec: &C,
+    output: &mut dyn Write,
+    compare: &mut impl FnMut(&C::Record, &C::Record) -> Ordering,
+) -> io::Result<()> {
+    let readers = runs
+        .iter()
+        .map(RunFile::open)
+        .collect::<io::Result<Vec<_>>>()?;
+    let records = readers
+        .into_iter()
+        .map(|mut reader| iter::from_fn(move || codec.read_record(&mut reader).transpose()));
+
+    let merged = merge::merge_by(
+        records,
+        |a: &io::Result<C::Record>, b: &io::Result<C::Record>| match (a, b) {
+            (Ok(a), Ok(b)) => compare(a, b),
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => Ordering::Equal,
+        },
+    );
+
+    for record in merged {
+        codec.write_record(output, &record?)?;
+    }
+
+    Ok(())
+}
+
+// A temporary file holding one sorted run, removed when dropped.
+struct RunFile {
+    path: PathBuf,
+}
+
+impl RunFile {
+    fn create(directory: &Path) -> io::Result<(Self, BufWriter<File>)> {
+        let name = format!(
+            "sort-run-{}-{}.tmp",
+            process::id(),
+            NEXT_RUN.fetch_add(1, AtomicOrdering::Relaxed)
+        );
+        let path = directory.join(name);
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        Ok((Self { path }, BufWriter::new(file)))
+    }
+
+    fn open(&self) -> io::Result<BufReader<File>> {
+        File::open(&self.path).map(BufReader::new)
+    }
+}
+
+impl Drop for RunFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Each test gets its own directory so it can check that no runs are left
+    // behind.
+    fn options(name: &str, chunk_size: usize, fan_in: usize) -> Options {
+        let temp_dir = env::temp_dir().join(format!("external-sort-{}-{name}", process::id()));
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        Options {
+            chunk_size,
+            fan_in,
+            temp_dir,
+        }
+    }
+
+    fn assert_cleaned_up(options: &Options) {
+        assert_eq!(fs::read_dir(&options.temp_dir).unwrap().count(), 0);
+        fs::remove_dir(&options.temp_dir).unwrap();
+    }
+
+    fn sort_lines(input: &str, options: &Options) -> String {
+        let mut output = Vec::new();
+        sort(input.as_bytes(), &mut output, &Lines, options).unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn sort_lines_in_memory() {
+        let options = options("in-memory", 100, 4);
+
+        assert_eq!(
+            sort_lines("pear\napple\nfig", &options),
+            "apple\nfig\npear\n"
+        );
+        assert_eq!(sort_lines("", &options), "");
+        assert_cleaned_up(&options);
+    }
+
+    #[test]
+    fn sort_lines_through_spilled_runs() {
+        let values = pseudo_random(5_000, 1_000);
+        let input: String = values.iter().map(|value| format!("{value:04}\n")).collect();
+        let mut expected: Vec<String> = values.iter().map(|value| format!("{value:04}")).collect();
+        expected.sort();
+
+        // 50 runs with a fan-in of 4 forces several intermediate merge passes.
+        for (chunk_size, fan_in) in [(100, 4), (100, 64), (4_999, 2), (5_000, 2)] {
+            let options = options("spilled", chunk_size, fan_in);
+
+            let output = sort_lines(&input, &options);
+
+            assert_eq!(output.lines().collect::<Vec<&str>>(), expected);
+            assert_cleaned_up(&options);
+        }
+    }
+
+    #[test]
+    fn sort_by_key_is_stable_across_runs() {
+        let options = options("stable", 3, 2);
+        let input: String = pseudo_random(200, 5)
+            .iter()
+            .enumerate()
+            .map(|(index, key)| format!("{key} {index}\n"))
+            .collect();
+        let mut expected: Vec<&str> = input.lines().collect();
+        expected.sort_by_key(|line| line.split(' ').next().unwrap().to_string());
+
+        let mut output = Vec::new();
+        sort_by_key(input.as_bytes(), &mut output, &Lines, &options, |line| {
+            line.split(' ').next().unwrap().to_string()
+        })
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .collect::<Vec<&str>>(),
+            expected
+        );
+        assert_cleaned_up(&options);
+    }
+
+    #[test]
+    fn sort_fixed_width_records() {
+        let options = options("fixed-width", 64, 3);
+        let values = pseudo_random(1_000, u64::MAX);
+        let input: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let mut expected = values.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        let mut output = Vec::new();
+        sort_by(
+            input.as_slice(),
+            &mut output,
+            &FixedWidth { width: 8 },
+            &options,
+            |a, b| b.cmp(a),
+        )
+        .unwrap();
+
+        let actual: Vec<u64> = output
+            .chunks(8)
+            .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(actual, expected);
+        assert_cleaned_up(&options);
+    }
+
+    #[test]
+    fn partial_fixed_width_record_is_an_error() {
+        let options = options("partial", 2, 2);
+        let input = [1u8, 2, 3, 4, 5];
+
+        let error = sort(
+            input.as_slice(),
+            Vec::new(),
+            &FixedWidth { width: 2 },
+            &options,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert_cleaned_up(&options);
+    }
+}
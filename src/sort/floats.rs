@@ -0,0 +1,285 @@
+use std::{cmp::Ordering, error::Error, fmt};
+
+use super::{intro_sort, radix_sort, radix_sort::RadixKey};
+
+// The generic sorts treat NaN as equal to everything, which is not a
+// consistent order: the result depends on where the NaNs started and can
+// leave numbers out of order around them. The sorts here never compare with
+// `<` and instead use one of two total orders.
+//
+// `sort` and `radix_sort` use IEEE 754 `totalOrder`, as `f64::total_cmp` does:
+// negative NaNs, negative infinity, the negative numbers, -0.0, 0.0, the
+// positive numbers, positive infinity, positive NaNs. The `_with` variants
+// keep that order for numbers but gather every NaN at one end, or refuse to
+// sort at all if there are any.
+
+pub trait Float: Copy + RadixKey {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    fn is_nan(&self) -> bool;
+}
+
+macro_rules! impl_float {
+    ($($float:ty),*) => {
+        $(
+            impl Float for $float {
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    <$float>::total_cmp(self, other)
+                }
+
+                fn is_nan(&self) -> bool {
+                    <$float>::is_nan(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NanPlacement {
+    First,
+    #[default]
+    Last,
+    // Leaves the slice untouched and reports the first NaN found.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanError {
+    pub index: usize,
+}
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot sort a NaN, found at index {}", self.index)
+    }
+}
+
+impl Error for NanError {}
+
+pub fn sort<F: Float>(array: &mut [F]) {
+    intro_sort::sort_by(array, F::total_cmp);
+}
+
+pub fn sort_by_key<T, F: Float>(array: &mut [T], mut key: impl FnMut(&T) -> F) {
+    intro_sort::sort_by(array, |a, b| key(a).total_cmp(&key(b)));
+}
+
+pub fn sort_with<F: Float>(array: &mut [F], nans: NanPlacement) -> Result<(), NanError> {
+    sort_by_key_with(array, nans, |value| *value)
+}
+
+pub fn sort_by_key_with<T, F: Float>(
+    array: &mut [T],
+    nans: NanPlacement,
+    mut key: impl FnMut(&T) -> F,
+) -> Result<(), NanError> {
+    match nans {
+        NanPlacement::First => intro_sort::sort_by(array, |a, b| {
+            let (a, b) = (key(a), key(b));
+            b.is_nan().cmp(&a.is_nan()).then(a.total_cmp(&b))
+        }),
+        NanPlacement::Last => intro_sort::sort_by(array, |a, b| {
+            let (a, b) = (key(a), key(b));
+            a.is_nan().cmp(&b.is_nan()).then(a.total_cmp(&b))
+        }),
+        NanPlacement::Error => {
+            if let Some(index) = array.iter().position(|value| key(value).is_nan()) {
+                return Err(NanError { index });
+            }
+
+            intro_sort::sort_by(array, |a, b| key(a).total_cmp(&key(b)));
+        }
+    }
+
+    Ok(())
+}
+
+// Sorts the floats' ordered integer keys with the LSD radix sort, so there are
+// no comparisons at all.
+pub fn radix_sort<F: Float>(array: &mut [F]) {
+    radix_sort::sort(array);
+}
+
+// In total order the negative NaNs come first and the positive ones last, so
+// after a radix sort one group is rotated across to join the other.
+pub fn radix_sort_with<F: Float>(array: &mut [F], nans: NanPlacement) -> Result<(), NanError> {
+    if nans == NanPlacement::Error {
+        if let Some(index) = array.iter().position(Float::is_nan) {
+            return Err(NanError { index });
+        }
+    }
+
+    radix_sort::sort(array);
+
+    let negative = array.iter().take_while(|value| value.is_nan()).count();
+    let positive = array[negative..]
+        .iter()
+        .rev()
+        .take_while(|value| value.is_nan())
+        .count();
+    let length = array.len();
+
+    match nans {
+        NanPlacement::First => array[negative..].rotate_right(positive),
+        NanPlacement::Last => array[..length - positive].rotate_left(negative),
+        NanPlacement::Error => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Arbitrary bit patterns cover every class of float, including NaNs of
+    // both signs with assorted payloads, mixed with the special values.
+    fn awkward_floats(length: usize) -> Vec<f64> {
+        let specials = [
+            f64::NAN,
+            -f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.0,
+            -0.0,
+            f64::MIN_POSITIVE,
+            -f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::MIN,
+            1.0,
+            -1.0,
+        ];
+
+        pseudo_random(length, u64::MAX)
+            .into_iter()
+            .map(f64::from_bits)
+            .chain(specials.iter().copied().cycle().take(length / 4))
+            .collect()
+    }
+
+    fn bits(array: &[f64]) -> Vec<u64> {
+        array.iter().map(|value| value.to_bits()).collect()
+    }
+
+    fn expected_with(array: &[f64], nans: NanPlacement) -> Vec<f64> {
+        let mut expected: Vec<f64> = array
+            .iter()
+            .copied()
+            .filter(|value| !value.is_nan())
+            .collect();
+        let mut nan_values: Vec<f64> = array
+            .iter()
+            .copied()
+            .filter(|value| value.is_nan())
+            .collect();
+        expected.sort_by(f64::total_cmp);
+        nan_values.sort_by(f64::total_cmp);
+
+        match nans {
+            NanPlacement::First => nan_values.into_iter().chain(expected).collect(),
+            _ => expected.into_iter().chain(nan_values).collect(),
+        }
+    }
+
+    #[test]
+    fn sort_in_total_order() {
+        let array = awkward_floats(5_000);
+        let mut expected = array.clone();
+        expected.sort_by(f64::total_cmp);
+
+        let mut actual = array.clone();
+        sort(actual.as_mut_slice());
+        assert_eq!(bits(&actual), bits(&expected));
+
+        let mut actual = array;
+        radix_sort(actual.as_mut_slice());
+        assert_eq!(bits(&actual), bits(&expected));
+    }
+
+    #[test]
+    fn sort_small_mixed_array() {
+        let mut array = [3.5, f64::NAN, -0.0, f64::NEG_INFINITY, 0.0, -f64::NAN, -2.0];
+
+        sort(&mut array);
+
+        assert!(array[0].is_nan() && array[0].is_sign_negative());
+        assert_eq!(array[1..6], [f64::NEG_INFINITY, -2.0, -0.0, 0.0, 3.5]);
+        assert!(array[4].is_sign_positive() && array[3].is_sign_negative());
+        assert!(array[6].is_nan() && array[6].is_sign_positive());
+    }
+
+    #[test]
+    fn nans_are_gathered_first_or_last() {
+        let array = awkward_floats(3_000);
+
+        for nans in [NanPlacement::First, NanPlacement::Last] {
+            let expected = expected_with(&array, nans);
+
+            let mut actual = array.clone();
+            sort_with(actual.as_mut_slice(), nans).unwrap();
+            assert_eq!(bits(&actual), bits(&expected), "{nans:?}");
+
+            let mut actual = array.clone();
+            radix_sort_with(actual.as_mut_slice(), nans).unwrap();
+            assert_eq!(bits(&actual), bits(&expected), "{nans:?}");
+        }
+    }
+
+    #[test]
+    fn nan_placement_error_reports_first_nan_and_leaves_slice() {
+        let array = [2.0, 1.0, -f64::NAN, f64::NAN];
+
+        let mut actual = array;
+        assert_eq!(
+            sort_with(&mut actual, NanPlacement::Error),
+            Err(NanError { index: 2 })
+        );
+        assert_eq!(bits(&actual), bits(&array));
+        assert_eq!(
+            radix_sort_with(&mut actual, NanPlacement::Error),
+            Err(NanError { index: 2 })
+        );
+        assert_eq!(bits(&actual), bits(&array));
+        assert_eq!(
+            NanError { index: 2 }.to_string(),
+            "cannot sort a NaN, found at index 2"
+        );
+
+        let mut actual = [2.0, -1.0, 0.5];
+        assert_eq!(radix_sort_with(&mut actual, NanPlacement::Error), Ok(()));
+        assert_eq!(actual, [-1.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn sort_f32_and_keys() {
+        let mut array: Vec<f32> = pseudo_random(2_000, 2_000)
+            .into_iter()
+            .map(|value| (value as f32 - 1_000.0) / 7.0)
+            .chain([f32::NAN, -0.0, f32::INFINITY])
+            .collect();
+        let mut expected = array.clone();
+        expected.sort_by(f32::total_cmp);
+
+        radix_sort(array.as_mut_slice());
+        assert!(array
+            .iter()
+            .zip(&expected)
+            .all(|(a, b)| a.to_bits() == b.to_bits()));
+
+        let mut records = vec![("c", 2.5), ("nan", f64::NAN), ("a", -1.0), ("b", 0.0)];
+        sort_by_key_with(records.as_mut_slice(), NanPlacement::First, |(_, value)| {
+            *value
+        })
+        .unwrap();
+        let names: Vec<&str> = records.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["nan", "a", "b", "c"]);
+
+        sort_by_key(records.as_mut_slice(), |(_, value)| -value);
+        let names: Vec<&str> = records.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["nan", "c", "b", "a"]);
+    }
+}
@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn sort_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    heapify_with_ops(array, ops);
+
+    for end in (1..array.len()).rev() {
+        ops.swap(array, 0, end);
+        sift_down_with_ops(&mut array[..end], 0, ops);
+    }
+}
+
+// Rearranges the slice into a max-heap, where every element is greater than or
+// equal to its children at `2i + 1` and `2i + 2`.
+pub fn heapify<T: PartialOrd>(array: &mut [T]) {
+    heapify_by(array, partial_compare);
+}
+
+// With a reversed comparator this builds a min-heap instead.
+pub fn heapify_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    heapify_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn heapify_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    for index in (0..array.len() / 2).rev() {
+        sift_down_with_ops(array, index, ops);
+    }
+}
+
+pub fn sift_down<T: PartialOrd>(heap: &mut [T], index: usize) {
+    sift_down_by(heap, index, partial_compare);
+}
+
+pub fn sift_down_by<T>(heap: &mut [T], index: usize, compare: impl FnMut(&T, &T) -> Ordering) {
+    sift_down_with_ops(heap, index, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn sift_down_with_ops<T>(heap: &mut [T], mut index: usize, ops: &mut impl SortOps<T>) {
+    loop {
+        let left = 2 * index + 1;
+        if left >= heap.len() {
+            break;
+        }
+
+        let right = left + 1;
+        let child = if right < heap.len() && ops.is_less(&heap[left], &heap[right]) {
+            right
+        } else {
+            left
+        };
+
+        if ops.is_less(&heap[index], &heap[child]) {
+            ops.swap(heap, index, child);
+            index = child;
+        } else {
+            break;
+        }
+    }
+}
+
+pub fn sift_up<T: PartialOrd>(heap: &mut [T], index: usize) {
+    sift_up_by(heap, index, partial_compare);
+}
+
+pub fn sift_up_by<T>(heap: &mut [T], index: usize, compare: impl FnMut(&T, &T) -> Ordering) {
+    sift_up_with_ops(heap, index, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn sift_up_with_ops<T>(heap: &mut [T], mut index: usize, ops: &mut impl SortOps<T>) {
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if ops.is_less(&heap[parent], &heap[index]) {
+            ops.swap(heap, parent, index);
+            index = parent;
+        } else {
+            break;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapSort;
+
+impl<T> Sorter<T> for HeapSort {
+    fn name(&self) -> &'static str {
+        "heap_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::heap_sort;
+
+    fn is_max_heap(heap: &[i32]) -> bool {
+        (1..heap.len()).all(|index| heap[(index - 1) / 2] >= heap[index])
+    }
+
+    #[test]
+    fn sort_empty_and_single_element_arrays() {
+        let mut empty: Vec<i32> = vec![];
+        let mut single = vec![1];
+
+        heap_sort::sort(empty.as_mut_slice());
+        heap_sort::sort(single.as_mut_slice());
+
+        assert!(empty.is_empty());
+        assert_eq!(single, vec![1]);
+    }
+
+    #[test]
+    fn heapify_builds_max_heap() {
+        let mut array = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+
+        heap_sort::heapify(array.as_mut_slice());
+
+        assert!(is_max_heap(&array));
+        assert_eq!(array[0], 22);
+    }
+
+    #[test]
+    fn sift_up_restores_heap_after_push() {
+        let mut heap = Vec::new();
+
+        for value in [4, 8, 1, 9, 3, 12, 7] {
+            heap.push(value);
+            let last = heap.len() - 1;
+            heap_sort::sift_up(heap.as_mut_slice(), last);
+
+            assert!(is_max_heap(&heap));
+        }
+
+        assert_eq!(heap[0], 12);
+    }
+
+    #[test]
+    fn sift_down_restores_heap_after_replacing_root() {
+        let mut heap = vec![22, 15, 20, 10, 9, 5, 10, 1, 2, 1];
+
+        heap[0] = 0;
+        heap_sort::sift_down(heap.as_mut_slice(), 0);
+
+        assert!(is_max_heap(&heap));
+        assert_eq!(heap[0], 20);
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        heap_sort::sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (4, "d"), (2, "b")];
+
+        heap_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+    }
+
+    #[test]
+    fn heapify_by_reversed_comparator_builds_min_heap() {
+        let mut array = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+
+        heap_sort::heapify_by(array.as_mut_slice(), |a: &i32, b: &i32| b.cmp(a));
+
+        assert!((1..array.len()).all(|index| array[(index - 1) / 2] <= array[index]));
+        assert_eq!(array[0], 1);
+    }
+}
@@ -0,0 +1,420 @@
+use std::cmp::Ordering;
+
+use super::{
+    heap_sort, insertion_sort, partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    random::XorShift,
+    sorter::{Complexity, Sorter},
+};
+
+const INSERTION_THRESHOLD: usize = 20;
+const NINTHER_THRESHOLD: usize = 50;
+const MAX_PIVOT_SWAPS: usize = 4 * 3;
+const PARTIAL_INSERTION_MAX_STEPS: usize = 5;
+const PARTIAL_INSERTION_MIN_LENGTH: usize = 50;
+
+// Pattern-defeating quicksort: a quicksort that falls back to insertion sort
+// for short slices and to heap sort once too many unbalanced partitions have
+// been seen, with shortcuts for sorted, reversed and duplicate-heavy inputs.
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub(crate) fn sort_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    let limit = usize::BITS - array.len().leading_zeros();
+    recurse(array, None, limit, ops);
+}
+
+fn recurse<'a, T>(
+    mut array: &'a mut [T],
+    mut predecessor: Option<&'a T>,
+    mut limit: u32,
+    ops: &mut impl SortOps<T>,
+) {
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        let length = array.len();
+
+        if length <= INSERTION_THRESHOLD {
+            insertion_sort::sort_with_ops(array, ops);
+            return;
+        }
+
+        if limit == 0 {
+            heap_sort::sort_with_ops(array, ops);
+            return;
+        }
+
+        if !was_balanced {
+            break_patterns(array, ops);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(array, ops);
+
+        if was_balanced && was_partitioned && likely_sorted && partial_insertion_sort(array, ops) {
+            return;
+        }
+
+        // Everything in this slice is at least the predecessor, so a pivot equal
+        // to it means the slice starts with a run of equal keys that can be
+        // split off in one pass instead of being partitioned again and again.
+        if let Some(predecessor) = predecessor {
+            if ops.compare(predecessor, &array[pivot]).is_ge() {
+                let equal = partition_equal(array, pivot, ops);
+                array = &mut std::mem::take(&mut array)[equal..];
+                continue;
+            }
+        }
+
+        let (middle, already_partitioned) = partition(array, pivot, ops);
+        was_partitioned = already_partitioned;
+
+        let (left, rest) = std::mem::take(&mut array).split_at_mut(middle);
+        let (pivot, right) = rest.split_at_mut(1);
+        let pivot = &pivot[0];
+        was_balanced = usize::min(left.len(), right.len()) >= length / 8;
+
+        if left.len() < right.len() {
+            recurse(left, predecessor, limit, ops);
+            array = right;
+            predecessor = Some(pivot);
+        } else {
+            recurse(right, Some(pivot), limit, ops);
+            array = left;
+        }
+    }
+}
+
+// Picks a median of three (or a median of medians for longer slices) and
+// reports whether the samples were already in order. If every sample was out
+// of order the slice is probably descending, so it is reversed up front.
+fn choose_pivot<T>(array: &mut [T], ops: &mut impl SortOps<T>) -> (usize, bool) {
+    let length = array.len();
+    let mut swaps = 0;
+
+    let mut a = length / 4;
+    let mut b = length / 4 * 2;
+    let mut c = length / 4 * 3;
+
+    if length >= NINTHER_THRESHOLD {
+        a = sort3(array, a - 1, a, a + 1, &mut swaps, ops);
+        b = sort3(array, b - 1, b, b + 1, &mut swaps, ops);
+        c = sort3(array, c - 1, c, c + 1, &mut swaps, ops);
+    }
+
+    let pivot = sort3(array, a, b, c, &mut swaps, ops);
+
+    if swaps < MAX_PIVOT_SWAPS {
+        (pivot, swaps == 0)
+    } else {
+        ops.reverse(array);
+        (length - 1 - pivot, true)
+    }
+}
+
+// Orders three indices by the values they point at and returns the middle one.
+fn sort3<T>(
+    array: &[T],
+    mut a: usize,
+    mut b: usize,
+    mut c: usize,
+    swaps: &mut usize,
+    ops: &mut impl SortOps<T>,
+) -> usize {
+    let mut sort2 = |x: &mut usize, y: &mut usize| {
+        if ops.compare(&array[*y], &array[*x]).is_lt() {
+            std::mem::swap(x, y);
+            *swaps += 1;
+        }
+    };
+
+    sort2(&mut a, &mut b);
+    sort2(&mut b, &mut c);
+    sort2(&mut a, &mut b);
+
+    b
+}
+
+// Gives up after a handful of out-of-order pairs, so it only finishes the job
+// on slices that were nearly sorted to begin with.
+fn partial_insertion_sort<T>(array: &mut [T], ops: &mut impl SortOps<T>) -> bool {
+    let length = array.len();
+    let mut i = 1;
+
+    for _ in 0..PARTIAL_INSERTION_MAX_STEPS {
+        while i < length && ops.compare(&array[i], &array[i - 1]).is_ge() {
+            i += 1;
+        }
+
+        if i == length {
+            return true;
+        }
+
+        if length < PARTIAL_INSERTION_MIN_LENGTH {
+            return false;
+        }
+
+        ops.swap(array, i - 1, i);
+        shift_tail(&mut array[..i], ops);
+        shift_head(&mut array[i..], ops);
+    }
+
+    false
+}
+
+fn shift_tail<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    let mut j = array.len().saturating_sub(1);
+    while j > 0 && ops.compare(&array[j], &array[j - 1]).is_lt() {
+        ops.swap(array, j - 1, j);
+        j -= 1;
+    }
+}
+
+fn shift_head<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    let mut j = 0;
+    while j + 1 < array.len() && ops.compare(&array[j + 1], &array[j]).is_lt() {
+        ops.swap(array, j, j + 1);
+        j += 1;
+    }
+}
+
+// Moves the pivot to the front, then swaps elements less than the pivot to the
+// left and the rest to the right. Returns the pivot's final index and whether
+// the slice was already partitioned, in which case no swaps were needed.
+fn partition<T>(array: &mut [T], pivot: usize, ops: &mut impl SortOps<T>) -> (usize, bool) {
+    ops.swap(array, 0, pivot);
+
+    let mut left = 1;
+    let mut right = array.len();
+    let mut was_partitioned = true;
+
+    loop {
+        while left < right && ops.compare(&array[left], &array[0]).is_lt() {
+            left += 1;
+        }
+        while left < right && ops.compare(&array[right - 1], &array[0]).is_ge() {
+            right -= 1;
+        }
+
+        if left >= right {
+            break;
+        }
+
+        right -= 1;
+        ops.swap(array, left, right);
+        left += 1;
+        was_partitioned = false;
+    }
+
+    ops.swap(array, 0, left - 1);
+    (left - 1, was_partitioned)
+}
+
+// Gathers the elements equal to the pivot at the front of the slice and
+// returns how many there are. Only valid when no element is less than the
+// pivot.
+fn partition_equal<T>(array: &mut [T], pivot: usize, ops: &mut impl SortOps<T>) -> usize {
+    ops.swap(array, 0, pivot);
+
+    let mut left = 1;
+    let mut right = array.len();
+
+    loop {
+        while left < right && ops.compare(&array[0], &array[left]).is_ge() {
+            left += 1;
+        }
+        while left < right && ops.compare(&array[0], &array[right - 1]).is_lt() {
+            right -= 1;
+        }
+
+        if left >= right {
+            break;
+        }
+
+        right -= 1;
+        ops.swap(array, left, right);
+        left += 1;
+    }
+
+    left
+}
+
+// Scatters a few elements around the middle so that inputs crafted against the
+// pivot choice stop producing unbalanced partitions.
+fn break_patterns<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    let length = array.len();
+    let mut rng = XorShift::with_seed(length as u64);
+    let middle = length / 4 * 2;
+
+    for offset in 0..3 {
+        let other = rng.below(length);
+        ops.swap(array, middle - 1 + offset, other);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntroSort;
+
+impl<T> Sorter<T> for IntroSort {
+    fn name(&self) -> &'static str {
+        "intro_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    fn assert_sorts(array: Vec<u64>) {
+        let mut array_1 = array;
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        sort(array_1.as_mut_slice());
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_empty_and_single_element_arrays() {
+        assert_sorts(vec![]);
+        assert_sorts(vec![1]);
+    }
+
+    #[test]
+    fn sort_large_patterned_arrays() {
+        assert_sorts((0..10_000).collect());
+        assert_sorts((0..10_000).rev().collect());
+        assert_sorts((0..5_000).chain((0..5_000).rev()).collect());
+        assert_sorts((0..10_000).map(|value| value % 37).collect());
+        assert_sorts(vec![3; 10_000]);
+    }
+
+    #[test]
+    fn sort_large_random_arrays() {
+        assert_sorts(pseudo_random(100_000, u64::MAX));
+        assert_sorts(pseudo_random(100_000, 5));
+    }
+
+    #[test]
+    fn sort_nearly_sorted_array() {
+        let mut array: Vec<u64> = (0..10_000).collect();
+        array.swap(10, 9_000);
+        array.swap(500, 501);
+
+        assert_sorts(array);
+    }
+
+    #[test]
+    fn heap_sort_fallback_sorts_when_limit_is_exhausted() {
+        let mut array_1 = pseudo_random(1_000, 100);
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        recurse(
+            array_1.as_mut_slice(),
+            None,
+            0,
+            &mut Probed::new(partial_compare, NoProbe),
+        );
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn partition_reports_already_partitioned_slices() {
+        let mut array = vec![5, 1, 2, 3, 4, 6, 7, 8, 9];
+
+        let (middle, was_partitioned) = partition(
+            array.as_mut_slice(),
+            0,
+            &mut Probed::new(partial_compare, NoProbe),
+        );
+
+        assert_eq!(middle, 4);
+        assert_eq!(array[middle], 5);
+        assert!(was_partitioned);
+    }
+
+    #[test]
+    fn partition_equal_gathers_keys_equal_to_pivot() {
+        let mut array = vec![2, 9, 2, 7, 2, 2, 8];
+
+        let equal = partition_equal(
+            array.as_mut_slice(),
+            0,
+            &mut Probed::new(partial_compare, NoProbe),
+        );
+
+        assert_eq!(equal, 4);
+        assert!(array[..equal].iter().all(|value| *value == 2));
+        assert!(array[equal..].iter().all(|value| *value > 2));
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = pseudo_random(10_000, 100);
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(1_000, u64::MAX)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+}
@@ -0,0 +1,410 @@
+use std::cmp::Ordering;
+
+use super::{heap_sort, partial_compare};
+
+// Both iterators merge any number of sources that are each already sorted,
+// pulling one item at a time, so they work on sources too large to hold at
+// once or even unbounded ones. Equal items come out in source order, and in
+// their original order within a source.
+//
+// `merge` keeps the next item of every source in a binary min-heap. The
+// tournament variant keeps them in a loser tree instead, which replays one
+// match per level after each item, about log2 k comparisons against the
+// heap's 2 log2 k, at the cost of visiting exhausted sources until the end.
+
+type Source<S> = <<S as IntoIterator>::Item as IntoIterator>::IntoIter;
+
+pub fn merge<S, T>(sources: S) -> Merge<Source<S>, impl FnMut(&T, &T) -> Ordering>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator<Item = T>,
+    T: PartialOrd,
+{
+    merge_by(sources, partial_compare)
+}
+
+pub fn merge_by_key<S, T, K: PartialOrd>(
+    sources: S,
+    mut key: impl FnMut(&T) -> K,
+) -> Merge<Source<S>, impl FnMut(&T, &T) -> Ordering>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator<Item = T>,
+{
+    merge_by(sources, move |a: &T, b: &T| {
+        partial_compare(&key(a), &key(b))
+    })
+}
+
+pub fn merge_by<S, F>(sources: S, compare: F) -> Merge<Source<S>, F>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator,
+    F: FnMut(&<S::Item as IntoIterator>::Item, &<S::Item as IntoIterator>::Item) -> Ordering,
+{
+    let mut sources: Vec<_> = sources.into_iter().map(IntoIterator::into_iter).collect();
+    let mut heap: Vec<_> = sources
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, source)| Some((source.next()?, index)))
+        .collect();
+
+    let mut compare = compare;
+    heap_sort::heapify_by(&mut heap, |a, b| order(&mut compare, a, b));
+
+    Merge {
+        sources,
+        heap,
+        compare,
+    }
+}
+
+pub struct Merge<I: Iterator, F> {
+    sources: Vec<I>,
+    // The next item of every source that has one, tagged with the source's
+    // index.
+    heap: Vec<(I::Item, usize)>,
+    compare: F,
+}
+
+// The heap primitives in `heap_sort` build max-heaps, so the comparison is
+// reversed to bring the smallest item, and of equal items the one from the
+// earliest source, to the root.
+fn order<T>(
+    compare: &mut impl FnMut(&T, &T) -> Ordering,
+    a: &(T, usize),
+    b: &(T, usize),
+) -> Ordering {
+    compare(&b.0, &a.0).then(b.1.cmp(&a.1))
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> Ordering> Iterator for Merge<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let Self {
+            sources,
+            heap,
+            compare,
+        } = self;
+        let (_, source) = heap.first()?;
+
+        let item = match sources[*source].next() {
+            Some(next) => std::mem::replace(&mut heap[0].0, next),
+            None => heap.swap_remove(0).0,
+        };
+        heap_sort::sift_down_by(heap, 0, |a, b| order(compare, a, b));
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.heap.len();
+        let sources = self.heap.iter().map(|(_, source)| &self.sources[*source]);
+
+        remaining(buffered, sources)
+    }
+}
+
+pub fn tournament_merge<S, T>(
+    sources: S,
+) -> TournamentMerge<Source<S>, impl FnMut(&T, &T) -> Ordering>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator<Item = T>,
+    T: PartialOrd,
+{
+    tournament_merge_by(sources, partial_compare)
+}
+
+pub fn tournament_merge_by_key<S, T, K: PartialOrd>(
+    sources: S,
+    mut key: impl FnMut(&T) -> K,
+) -> TournamentMerge<Source<S>, impl FnMut(&T, &T) -> Ordering>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator<Item = T>,
+{
+    tournament_merge_by(sources, move |a: &T, b: &T| {
+        partial_compare(&key(a), &key(b))
+    })
+}
+
+pub fn tournament_merge_by<S, F>(sources: S, compare: F) -> TournamentMerge<Source<S>, F>
+where
+    S: IntoIterator,
+    S::Item: IntoIterator,
+    F: FnMut(&<S::Item as IntoIterator>::Item, &<S::Item as IntoIterator>::Item) -> Ordering,
+{
+    let mut sources: Vec<_> = sources.into_iter().map(IntoIterator::into_iter).collect();
+    let heads = sources.iter_mut().map(Iterator::next).collect();
+    let width = sources.len();
+
+    let mut merge = TournamentMerge {
+        sources,
+        heads,
+        losers: vec![0; width.max(1)],
+        compare,
+    };
+
+    // Plays the first round bottom up, with source `i` as leaf `width + i` of
+    // an implicit binary tree whose internal nodes are `1..width`.
+    let mut winners = vec![0; 2 * width];
+    for (source, winner) in winners[width..].iter_mut().enumerate() {
+        *winner = source;
+    }
+    for node in (1..width).rev() {
+        let (a, b) = (winners[2 * node], winners[2 * node + 1]);
+        let (winner, loser) = if merge.beats(a, b) { (a, b) } else { (b, a) };
+        winners[node] = winner;
+        merge.losers[node] = loser;
+    }
+    if width > 0 {
+        merge.losers[0] = winners[1];
+    }
+
+    merge
+}
+
+pub struct TournamentMerge<I: Iterator, F> {
+    sources: Vec<I>,
+    // The next item of each source, empty once the source is exhausted.
+    heads: Vec<Option<I::Item>>,
+    // The source that lost the last match played at each internal node, with
+    // the overall winner kept in the otherwise unused slot 0.
+    losers: Vec<usize>,
+    compare: F,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> Ordering> TournamentMerge<I, F> {
+    // Exhausted sources lose every match, and ties go to the earlier source.
+    fn beats(&mut self, a: usize, b: usize) -> bool {
+        match (&self.heads[a], &self.heads[b]) {
+            (Some(x), Some(y)) => (self.compare)(x, y).then(a.cmp(&b)).is_lt(),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> Ordering> Iterator for TournamentMerge<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let width = self.sources.len();
+        let mut winner = self.losers[0];
+        let item = self.heads.get_mut(winner)?.take()?;
+        self.heads[winner] = self.sources[winner].next();
+
+        // Only the matches on the path from the winner's leaf to the root can
+        // have changed.
+        let mut node = (winner + width) / 2;
+        while node > 0 {
+            if self.beats(self.losers[node], winner) {
+                std::mem::swap(&mut self.losers[node], &mut winner);
+            }
+            node /= 2;
+        }
+        self.losers[0] = winner;
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let live = (0..self.sources.len()).filter(|source| self.heads[*source].is_some());
+
+        remaining(
+            live.clone().count(),
+            live.map(|source| &self.sources[source]),
+        )
+    }
+}
+
+// Adds the items already pulled to what the sources that still have items say
+// they hold.
+fn remaining<'a, I: Iterator + 'a>(
+    buffered: usize,
+    sources: impl Iterator<Item = &'a I>,
+) -> (usize, Option<usize>) {
+    sources.fold((buffered, Some(buffered)), |(lower, upper), source| {
+        let (source_lower, source_upper) = source.size_hint();
+
+        (
+            lower.saturating_add(source_lower),
+            upper.zip(source_upper).and_then(|(a, b)| a.checked_add(b)),
+        )
+    })
+}
+
+pub fn merge_into<T: PartialOrd + Clone>(left: &[T], right: &[T], output: &mut [T]) {
+    merge_into_by(left, right, output, partial_compare);
+}
+
+pub fn merge_into_by_key<T: Clone, K: PartialOrd>(
+    left: &[T],
+    right: &[T],
+    output: &mut [T],
+    mut key: impl FnMut(&T) -> K,
+) {
+    merge_into_by(left, right, output, |a, b| {
+        partial_compare(&key(a), &key(b))
+    });
+}
+
+// Merges two sorted slices into `output`, which must be exactly as long as
+// both together. Ties are taken from `left` first.
+pub fn merge_into_by<T: Clone>(
+    left: &[T],
+    right: &[T],
+    output: &mut [T],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    assert_eq!(
+        output.len(),
+        left.len() + right.len(),
+        "output must hold exactly the {} elements being merged",
+        left.len() + right.len()
+    );
+
+    let mut i = 0;
+    let mut j = 0;
+    for slot in output.iter_mut() {
+        let take_right =
+            i == left.len() || (j < right.len() && compare(&right[j], &left[i]).is_lt());
+
+        if take_right {
+            slot.clone_from(&right[j]);
+            j += 1;
+        } else {
+            slot.clone_from(&left[i]);
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Splits random values into `count` sorted shards of uneven lengths,
+    // including some empty ones.
+    fn shards(count: usize, modulus: u64) -> Vec<Vec<u64>> {
+        let lengths = pseudo_random(count, 200);
+        let values = pseudo_random(lengths.iter().sum::<u64>() as usize, modulus);
+
+        let mut start = 0;
+        lengths
+            .iter()
+            .map(|length| {
+                let mut shard = values[start..start + *length as usize].to_vec();
+                shard.sort();
+                start += *length as usize;
+                shard
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merges_sorted_shards() {
+        for count in [0, 1, 2, 3, 7, 64, 100] {
+            let shards = shards(count, 1_000);
+            let mut expected: Vec<u64> = shards.concat();
+            expected.sort();
+
+            let heap = merge(shards.clone());
+            assert_eq!(heap.size_hint(), (expected.len(), Some(expected.len())));
+            assert_eq!(heap.collect::<Vec<_>>(), expected, "{count}");
+
+            let tournament = tournament_merge(shards.clone());
+            assert_eq!(
+                tournament.size_hint(),
+                (expected.len(), Some(expected.len()))
+            );
+            assert_eq!(tournament.collect::<Vec<_>>(), expected, "{count}");
+        }
+    }
+
+    #[test]
+    fn equal_items_keep_source_order() {
+        let shards: Vec<Vec<(u64, usize, usize)>> = shards(20, 10)
+            .into_iter()
+            .enumerate()
+            .map(|(source, shard)| {
+                shard
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, key)| (key, source, position))
+                    .collect()
+            })
+            .collect();
+        let mut expected: Vec<_> = shards.concat();
+        expected.sort();
+
+        let heap: Vec<_> = merge_by_key(shards.clone(), |(key, _, _)| *key).collect();
+        let tournament: Vec<_> = tournament_merge_by_key(shards, |(key, _, _)| *key).collect();
+
+        assert_eq!(heap, expected);
+        assert_eq!(tournament, expected);
+    }
+
+    #[test]
+    fn merges_unbounded_sources_lazily() {
+        let sources = [(0..).step_by(3), (1..).step_by(3), (2..).step_by(3)];
+
+        let heap: Vec<u64> = merge(sources.clone()).take(1_000).collect();
+        let tournament: Vec<u64> = tournament_merge(sources).take(1_000).collect();
+
+        assert_eq!(heap, (0..1_000).collect::<Vec<_>>());
+        assert_eq!(tournament, heap);
+    }
+
+    #[test]
+    fn merges_by_descending_comparator() {
+        let sources = vec![vec![9, 5, 1], vec![8, 8, 2], vec![], vec![10]];
+
+        let heap: Vec<i32> = merge_by(sources.clone(), |a, b| b.cmp(a)).collect();
+        let tournament: Vec<i32> = tournament_merge_by(sources, |a, b| b.cmp(a)).collect();
+
+        assert_eq!(heap, [10, 9, 8, 8, 5, 2, 1]);
+        assert_eq!(tournament, heap);
+    }
+
+    #[test]
+    fn merge_into_interleaves_two_slices() {
+        let mut left = pseudo_random(300, 50);
+        let mut right = pseudo_random(123, 60);
+        left.sort();
+        right.sort();
+        let mut expected = [left.clone(), right.clone()].concat();
+        expected.sort();
+
+        let mut output = vec![0; 423];
+        merge_into(&left, &right, &mut output);
+        assert_eq!(output, expected);
+
+        let mut output = vec![0; 300];
+        merge_into(&left, &[], &mut output);
+        assert_eq!(output, left);
+    }
+
+    #[test]
+    fn merge_into_takes_ties_from_left() {
+        let left = [(1, 'a'), (2, 'a'), (2, 'b')];
+        let right = [(0, 'x'), (2, 'x'), (3, 'x')];
+        let mut output = [(0, ' '); 6];
+
+        merge_into_by_key(&left, &right, &mut output, |(key, _)| *key);
+
+        assert_eq!(
+            output,
+            [(0, 'x'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'x'), (3, 'x')]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "output must hold exactly the 3 elements being merged")]
+    fn merge_into_rejects_wrong_output_length() {
+        merge_into(&[1, 2], &[3], &mut [0; 4]);
+    }
+}
@@ -0,0 +1,361 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+pub fn sort<T: PartialOrd + Clone>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by<T: Clone>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_top_down_by(array, compare);
+}
+
+pub fn sort_by_key<T: Clone, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_top_down<T: PartialOrd + Clone>(array: &mut [T]) {
+    sort_top_down_by(array, partial_compare);
+}
+
+pub fn sort_top_down_by<T: Clone>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    let mut buffer = Vec::with_capacity(array.len() / 2);
+    sort_with_buffer_by(array, &mut buffer, compare);
+}
+
+pub fn sort_bottom_up<T: PartialOrd + Clone>(array: &mut [T]) {
+    sort_bottom_up_by(array, partial_compare);
+}
+
+pub fn sort_bottom_up_by<T: Clone>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    let mut buffer = Vec::with_capacity(array.len() / 2);
+    sort_bottom_up_with_buffer_by(array, &mut buffer, compare);
+}
+
+pub fn sort_with_buffer<T: PartialOrd + Clone>(array: &mut [T], buffer: &mut Vec<T>) {
+    sort_with_buffer_by(array, buffer, partial_compare);
+}
+
+// The buffer is only used as scratch space: it is cleared before use and keeps
+// its capacity afterwards, so reusing it across calls avoids reallocating.
+pub fn sort_with_buffer_by<T: Clone>(
+    array: &mut [T],
+    buffer: &mut Vec<T>,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    top_down(array, buffer, &mut Probed::new(compare, NoProbe));
+}
+
+fn top_down<T: Clone>(array: &mut [T], buffer: &mut Vec<T>, ops: &mut impl SortOps<T>) {
+    buffer.clear();
+    buffer.reserve(array.len() / 2);
+
+    split_and_merge(array, buffer, ops);
+    ops.clear_buffer(buffer);
+}
+
+pub fn sort_bottom_up_with_buffer<T: PartialOrd + Clone>(array: &mut [T], buffer: &mut Vec<T>) {
+    sort_bottom_up_with_buffer_by(array, buffer, partial_compare);
+}
+
+pub fn sort_bottom_up_with_buffer_by<T: Clone>(
+    array: &mut [T],
+    buffer: &mut Vec<T>,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    bottom_up(array, buffer, &mut Probed::new(compare, NoProbe));
+}
+
+fn bottom_up<T: Clone>(array: &mut [T], buffer: &mut Vec<T>, ops: &mut impl SortOps<T>) {
+    buffer.clear();
+    buffer.reserve(array.len() / 2);
+
+    let length = array.len();
+    let mut width = 1;
+    while width < length {
+        let mut start = 0;
+        while start + width < length {
+            let end = usize::min(start + 2 * width, length);
+            merge(&mut array[start..end], width, buffer, ops);
+            start = end;
+        }
+
+        width *= 2;
+    }
+
+    ops.clear_buffer(buffer);
+}
+
+fn split_and_merge<T: Clone>(array: &mut [T], buffer: &mut Vec<T>, ops: &mut impl SortOps<T>) {
+    if array.len() < 2 {
+        return;
+    }
+
+    let middle = array.len() / 2;
+    split_and_merge(&mut array[..middle], buffer, ops);
+    split_and_merge(&mut array[middle..], buffer, ops);
+    merge(array, middle, buffer, ops);
+}
+
+// Merges the sorted runs `array[..middle]` and `array[middle..]`. Only the left
+// run is copied out; right elements are swapped down into place, which is safe
+// because the write index never overtakes the right read index.
+pub(crate) fn merge<T: Clone>(
+    array: &mut [T],
+    middle: usize,
+    buffer: &mut Vec<T>,
+    ops: &mut impl SortOps<T>,
+) {
+    if ops.compare(&array[middle - 1], &array[middle]).is_le() {
+        return;
+    }
+
+    ops.fill_buffer(buffer, &array[..middle]);
+
+    let mut merge = MergeLow {
+        array,
+        buffer,
+        left: 0,
+        right: middle,
+        write: 0,
+    };
+
+    while merge.left < merge.buffer.len() && merge.right < merge.array.len() {
+        if ops.is_less(&merge.array[merge.right], &merge.buffer[merge.left]) {
+            ops.swap(merge.array, merge.write, merge.right);
+            merge.right += 1;
+        } else {
+            ops.write(merge.array, merge.write, merge.buffer[merge.left].clone());
+            merge.left += 1;
+        }
+
+        merge.write += 1;
+    }
+
+    while merge.left < merge.buffer.len() {
+        ops.write(merge.array, merge.write, merge.buffer[merge.left].clone());
+        merge.left += 1;
+        merge.write += 1;
+    }
+}
+
+// A front-to-back merge whose left run has been copied out to `buffer`. The
+// merged output fills `array[..write]`, and `array[write..right]` holds stale
+// copies of the buffered elements still to be merged, `buffer[left..]`.
+// Dropping the merge writes those back over the stale copies, so if the
+// comparator panics part way the slice still holds every element exactly
+// once. A finished merge has nothing left to write.
+pub(crate) struct MergeLow<'a, T: Clone> {
+    pub(crate) array: &'a mut [T],
+    pub(crate) buffer: &'a [T],
+    pub(crate) left: usize,
+    pub(crate) right: usize,
+    pub(crate) write: usize,
+}
+
+impl<T: Clone> Drop for MergeLow<'_, T> {
+    fn drop(&mut self) {
+        self.array[self.write..self.right].clone_from_slice(&self.buffer[self.left..]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeSort;
+
+impl<T: Clone> Sorter<T> for MergeSort {
+    fn name(&self) -> &'static str {
+        "merge_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        false
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_top_down_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        let mut buffer = Vec::with_capacity(array.len() / 2);
+        top_down(array, &mut buffer, &mut Probed::new(compare, probe));
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BottomUpMergeSort;
+
+impl<T: Clone> Sorter<T> for BottomUpMergeSort {
+    fn name(&self) -> &'static str {
+        "bottom_up_merge_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        false
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_bottom_up_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        let mut buffer = Vec::with_capacity(array.len() / 2);
+        bottom_up(array, &mut buffer, &mut Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{conformance::Keyed, merge_sort};
+    use crate::testing::pseudo_random;
+
+    fn variants() -> Vec<fn(&mut [u64])> {
+        vec![
+            merge_sort::sort,
+            merge_sort::sort_top_down,
+            merge_sort::sort_bottom_up,
+            |array| merge_sort::sort_with_buffer(array, &mut Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn sort_empty_and_single_element_arrays() {
+        let mut empty: Vec<u64> = vec![];
+        let mut single = vec![1];
+
+        for variant in variants() {
+            variant(empty.as_mut_slice());
+            variant(single.as_mut_slice());
+        }
+
+        assert!(empty.is_empty());
+        assert_eq!(single, vec![1]);
+    }
+
+    #[test]
+    fn sort_duplicate_heavy_array() {
+        for variant in variants() {
+            let mut array_1 = pseudo_random(1_000, 4);
+            let mut array_2 = array_1.clone();
+
+            array_2.sort();
+            variant(array_1.as_mut_slice());
+
+            assert_eq!(array_1, array_2);
+        }
+    }
+
+    #[test]
+    fn sort_large_array() {
+        for variant in variants() {
+            let mut array_1 = pseudo_random(100_003, u64::MAX);
+            let mut array_2 = array_1.clone();
+
+            array_2.sort();
+            variant(array_1.as_mut_slice());
+
+            assert_eq!(array_1, array_2);
+        }
+    }
+
+    #[test]
+    fn top_down_and_bottom_up_are_stable() {
+        let array = Keyed::tag(pseudo_random(500, 10));
+
+        let mut top_down = array.clone();
+        let mut bottom_up = array.clone();
+        merge_sort::sort_top_down(top_down.as_mut_slice());
+        merge_sort::sort_bottom_up(bottom_up.as_mut_slice());
+
+        for sorted in [top_down, bottom_up] {
+            for pair in sorted.windows(2) {
+                assert!(pair[0].key <= pair[1].key);
+                if pair[0].key == pair[1].key {
+                    assert!(pair[0].index < pair[1].index);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn buffer_is_reused_between_calls() {
+        let mut buffer = Vec::new();
+        let mut array_1 = pseudo_random(1_000, 100);
+        let mut array_2 = pseudo_random(200, 100);
+
+        merge_sort::sort_with_buffer(array_1.as_mut_slice(), &mut buffer);
+        let capacity = buffer.capacity();
+        merge_sort::sort_bottom_up_with_buffer(array_2.as_mut_slice(), &mut buffer);
+
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), capacity);
+        assert!(array_1.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(array_2.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = pseudo_random(1_000, 50);
+        let mut array_2 = array_1.clone();
+        let mut array_3 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        merge_sort::sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+        merge_sort::sort_bottom_up_by(array_3.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+        assert_eq!(array_3, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(1_000, 10)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        merge_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+}
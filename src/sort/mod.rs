@@ -1,3 +1,51 @@
+use std::cmp::Ordering;
+
+pub mod analysis;
 pub mod bubble_sort;
+pub mod bucket_sort;
+pub mod counting_sort;
+pub mod cycle_sort;
+pub mod external;
+pub mod floats;
+pub mod heap_sort;
 pub mod insertion_sort;
+pub mod intro_sort;
+pub mod merge;
+pub mod merge_sort;
+pub mod network;
+pub mod parallel;
+pub mod patience;
+pub mod probe;
+pub mod quick_sort;
+pub mod radix_sort;
+pub mod random;
+pub mod select;
 pub mod selection_sort;
+pub mod shell_sort;
+pub mod sorter;
+pub mod stats;
+pub mod strings;
+pub mod tim_sort;
+pub mod trace;
+
+#[cfg(test)]
+mod conformance;
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    intro_sort::sort(array);
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    intro_sort::sort_by(array, compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], key: impl FnMut(&T) -> K) {
+    intro_sort::sort_by_key(array, key);
+}
+
+// Incomparable values such as NaN are treated as equal, which is exactly how
+// the `<` and `>` operators behave for them, so `sort` and `sort_by` with this
+// comparator always agree.
+pub(crate) fn partial_compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
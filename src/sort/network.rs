@@ -0,0 +1,366 @@
+use std::cmp::Ordering;
+
+use super::partial_compare;
+
+// A sorting network is a fixed sequence of compare-exchanges `(i, j)`, each of
+// which leaves the smaller of the two elements at `i` and the larger at `j`.
+// The sequence does not depend on the data, so there are no loops to predict
+// and the compiler can unroll the whole thing for a fixed `N`. Networks are
+// not stable.
+
+pub fn sort<T: PartialOrd, const N: usize>(array: &mut [T; N]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd, const N: usize>(
+    array: &mut [T; N],
+    mut key: impl FnMut(&T) -> K,
+) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+// Uses the smallest network known for `N`, which is proven optimal up to 12
+// elements. Sizes past 16 are rejected at compile time.
+pub fn sort_by<T, const N: usize>(array: &mut [T; N], compare: impl FnMut(&T, &T) -> Ordering) {
+    const {
+        assert!(
+            N <= 16,
+            "sorting networks are only provided for up to 16 elements"
+        );
+    }
+
+    apply_by(array, NETWORKS[N], compare);
+}
+
+// The network used by `sort` for `width` elements, if there is one.
+pub fn network(width: usize) -> Option<&'static [(usize, usize)]> {
+    NETWORKS.get(width).copied()
+}
+
+pub fn apply<T: PartialOrd>(array: &mut [T], network: &[(usize, usize)]) {
+    apply_by(array, network, partial_compare);
+}
+
+pub fn apply_by<T>(
+    array: &mut [T],
+    network: &[(usize, usize)],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    for &(i, j) in network {
+        if compare(&array[j], &array[i]).is_lt() {
+            array.swap(i, j);
+        }
+    }
+}
+
+// Batcher's bitonic sorter. It needs `n/2 log2 n (log2 n + 1) / 2` comparators,
+// more than the best known networks, but can be generated for any power of
+// two. Each merge starts by comparing the two halves back to front, which
+// sorts the pair of ascending runs the way the classic version does by
+// reversing one of them, so every comparator points the same way.
+pub fn bitonic(width: usize) -> Vec<(usize, usize)> {
+    assert!(
+        width.is_power_of_two(),
+        "bitonic networks need a power of two width, got {width}"
+    );
+
+    let mut network = Vec::new();
+    let mut size = 2;
+    while size <= width {
+        for start in (0..width).step_by(size) {
+            for i in 0..size / 2 {
+                network.push((start + i, start + size - 1 - i));
+            }
+        }
+
+        let mut half = size / 4;
+        while half > 0 {
+            for start in (0..width).step_by(2 * half) {
+                for i in start..start + half {
+                    network.push((i, i + half));
+                }
+            }
+            half /= 2;
+        }
+
+        size *= 2;
+    }
+
+    network
+}
+
+// Checks that a network sorts every input of `width` elements. By the 0-1
+// principle it is enough to try every input made only of zeros and ones, so
+// this runs the network over all `2^width` bit patterns: practical up to
+// twenty or so elements.
+pub fn verify(width: usize, network: &[(usize, usize)]) -> bool {
+    assert!(
+        width < u64::BITS as usize,
+        "cannot verify networks of {width} elements"
+    );
+    if let Some(&(i, j)) = network.iter().find(|(i, j)| *i >= width || *j >= width) {
+        panic!("comparator ({i}, {j}) is out of bounds for a width of {width}");
+    }
+
+    (0..1u64 << width).all(|input| {
+        let mut bits = input;
+        for &(i, j) in network {
+            if bits >> i & 1 == 1 && bits >> j & 1 == 0 {
+                bits ^= 1 << i | 1 << j;
+            }
+        }
+
+        // Sorted means every zero below every one.
+        let ones = bits.count_ones() as usize;
+        bits == ((1 << ones) - 1) << (width - ones)
+    })
+}
+
+// The smallest networks known for each width, one layer of independent
+// comparators per line.
+#[rustfmt::skip]
+const NETWORKS: [&[(usize, usize)]; 17] = [
+    &[],
+    &[],
+    &[
+        (0, 1),
+    ],
+    &[
+        (0, 2),
+        (0, 1),
+        (1, 2),
+    ],
+    &[
+        (0, 2), (1, 3),
+        (0, 1), (2, 3),
+        (1, 2),
+    ],
+    &[
+        (0, 3), (1, 4),
+        (0, 2), (1, 3),
+        (0, 1), (2, 4),
+        (1, 2), (3, 4),
+        (2, 3),
+    ],
+    &[
+        (0, 5), (1, 3), (2, 4),
+        (1, 2), (3, 4),
+        (0, 3), (2, 5),
+        (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ],
+    &[
+        (0, 6), (2, 3), (4, 5),
+        (0, 2), (1, 4), (3, 6),
+        (0, 1), (2, 5), (3, 4),
+        (1, 2), (4, 6),
+        (2, 3), (4, 5),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (2, 4), (3, 5),
+        (1, 4), (3, 6),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 3), (1, 7), (2, 5), (4, 8),
+        (0, 7), (2, 4), (3, 8), (5, 6),
+        (0, 2), (1, 3), (4, 5), (7, 8),
+        (1, 4), (3, 6), (5, 7),
+        (0, 1), (2, 4), (3, 5), (6, 8),
+        (2, 3), (4, 5), (6, 7),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+        (0, 2), (1, 4), (5, 8), (7, 9),
+        (0, 3), (2, 4), (5, 7), (6, 9),
+        (0, 1), (3, 6), (8, 9),
+        (1, 5), (2, 3), (4, 8), (6, 7),
+        (1, 2), (3, 5), (4, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+        (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+        (1, 3), (2, 5), (4, 7), (8, 10),
+        (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+        (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+        (2, 4), (3, 6), (5, 7), (8, 9),
+        (1, 2), (3, 4), (5, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+    ],
+    &[
+        (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+        (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+        (0, 2), (1, 6), (5, 10), (9, 11),
+        (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+        (1, 4), (3, 5), (6, 8), (7, 10),
+        (1, 3), (2, 5), (6, 9), (8, 10),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (4, 6), (5, 7),
+        (3, 4), (5, 6), (7, 8),
+    ],
+    &[
+        (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+        (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+        (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+        (4, 6), (5, 9), (8, 11), (10, 12),
+        (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+        (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+        (1, 3), (2, 4), (5, 6), (9, 10),
+        (1, 2), (3, 4), (5, 7), (6, 8),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 8), (5, 9), (10, 12), (11, 13),
+        (0, 4), (1, 2), (3, 7), (5, 8), (6, 10), (9, 13), (11, 12),
+        (0, 6), (1, 5), (3, 9), (4, 10), (7, 13), (8, 12),
+        (2, 10), (3, 11), (4, 6), (7, 9),
+        (1, 3), (2, 8), (5, 11), (6, 7), (10, 12),
+        (1, 4), (2, 6), (3, 5), (7, 11), (8, 10), (9, 12),
+        (2, 4), (3, 6), (5, 8), (7, 10), (9, 11),
+        (3, 4), (5, 6), (7, 8), (9, 10),
+        (6, 7),
+    ],
+    &[
+        (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    &[
+        (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    fn assert_sorts<const N: usize>() {
+        let values = pseudo_random(N * 50, 6);
+
+        for chunk in values.chunks_exact(N.max(1)).take(50) {
+            let mut array: [u64; N] = chunk[..N].try_into().unwrap();
+            let mut expected = array;
+            expected.sort();
+
+            sort(&mut array);
+
+            assert_eq!(array, expected, "N = {N}");
+        }
+    }
+
+    #[test]
+    fn sort_every_supported_size() {
+        assert_sorts::<0>();
+        assert_sorts::<1>();
+        assert_sorts::<2>();
+        assert_sorts::<3>();
+        assert_sorts::<4>();
+        assert_sorts::<5>();
+        assert_sorts::<6>();
+        assert_sorts::<7>();
+        assert_sorts::<8>();
+        assert_sorts::<9>();
+        assert_sorts::<10>();
+        assert_sorts::<11>();
+        assert_sorts::<12>();
+        assert_sorts::<13>();
+        assert_sorts::<14>();
+        assert_sorts::<15>();
+        assert_sorts::<16>();
+    }
+
+    #[test]
+    fn sort_by_comparator_and_key() {
+        let mut array = [10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+        sort_by(&mut array, |a, b| b.cmp(a));
+        assert_eq!(array, [22, 20, 15, 10, 10, 9, 5, 2, 1, 1]);
+
+        let mut words = ["pear", "fig", "banana", "kiwi"];
+        sort_by_key(&mut words, |word| word.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "banana");
+    }
+
+    #[test]
+    fn networks_are_valid_and_as_small_as_known() {
+        let sizes = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+
+        for (width, size) in sizes.into_iter().enumerate() {
+            let network = network(width).unwrap();
+
+            assert_eq!(network.len(), size, "width {width}");
+            assert!(network.iter().all(|(i, j)| i < j), "width {width}");
+            assert!(verify(width, network), "width {width}");
+        }
+        assert!(network(17).is_none());
+    }
+
+    #[test]
+    fn bitonic_networks_sort() {
+        for width in [1, 2, 4, 8, 16] {
+            let network = bitonic(width);
+
+            assert_eq!(
+                network.len(),
+                width / 2 * width.ilog2() as usize * (width.ilog2() as usize + 1) / 2
+            );
+            assert!(verify(width, &network), "width {width}");
+        }
+
+        let mut array = pseudo_random(64, 1_000);
+        let mut expected = array.clone();
+        expected.sort();
+        apply(array.as_mut_slice(), &bitonic(64));
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "bitonic networks need a power of two width, got 12")]
+    fn bitonic_rejects_other_widths() {
+        bitonic(12);
+    }
+
+    #[test]
+    fn verify_rejects_broken_networks() {
+        let mut broken = network(8).unwrap().to_vec();
+        assert!(verify(8, &broken));
+
+        broken.pop();
+        assert!(!verify(8, &broken));
+        assert!(!verify(9, network(8).unwrap()));
+        assert!(!verify(2, &[]));
+        assert!(verify(1, &[]));
+    }
+
+    #[test]
+    #[should_panic(expected = "comparator (3, 4) is out of bounds for a width of 4")]
+    fn verify_rejects_out_of_bounds_comparators() {
+        verify(4, &[(0, 1), (3, 4)]);
+    }
+}
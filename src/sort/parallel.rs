@@ -0,0 +1,341 @@
+use std::{cmp::Ordering, num::NonZeroUsize, thread};
+
+use super::{
+    heap_sort, insertion_sort, merge_sort, partial_compare,
+    probe::{NoProbe, Probed},
+    quick_sort,
+};
+
+// Below this length a slice is never split across threads, since spawning
+// would cost more than sorting it.
+const MIN_PARALLEL_LENGTH: usize = 1 << 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parallelism {
+    // The most threads a single sort may use, including the calling thread.
+    pub threads: usize,
+    // Slices of at most this length are finished with insertion sort.
+    pub cutoff: usize,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            cutoff: 32,
+        }
+    }
+}
+
+pub fn merge_sort<T: PartialOrd + Clone + Send>(array: &mut [T]) {
+    merge_sort_by(array, partial_compare);
+}
+
+pub fn merge_sort_by<T: Clone + Send>(
+    array: &mut [T],
+    compare: impl Fn(&T, &T) -> Ordering + Sync,
+) {
+    merge_sort_with_by(array, Parallelism::default(), compare);
+}
+
+pub fn merge_sort_by_key<T: Clone + Send, K: PartialOrd>(
+    array: &mut [T],
+    key: impl Fn(&T) -> K + Sync,
+) {
+    merge_sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn merge_sort_with<T: PartialOrd + Clone + Send>(array: &mut [T], parallelism: Parallelism) {
+    merge_sort_with_by(array, parallelism, partial_compare);
+}
+
+pub fn merge_sort_with_by<T: Clone + Send>(
+    array: &mut [T],
+    parallelism: Parallelism,
+    compare: impl Fn(&T, &T) -> Ordering + Sync,
+) {
+    assert!(parallelism.threads > 0, "threads must be at least 1");
+
+    let mut buffer = Vec::with_capacity(array.len() / 2);
+    parallel_merge_sort(
+        array,
+        &mut buffer,
+        parallelism.threads,
+        parallelism.cutoff,
+        &compare,
+    );
+}
+
+pub fn quick_sort<T: PartialOrd + Send>(array: &mut [T]) {
+    quick_sort_by(array, partial_compare);
+}
+
+pub fn quick_sort_by<T: Send>(array: &mut [T], compare: impl Fn(&T, &T) -> Ordering + Sync) {
+    quick_sort_with_by(array, Parallelism::default(), compare);
+}
+
+pub fn quick_sort_by_key<T: Send, K: PartialOrd>(array: &mut [T], key: impl Fn(&T) -> K + Sync) {
+    quick_sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn quick_sort_with<T: PartialOrd + Send>(array: &mut [T], parallelism: Parallelism) {
+    quick_sort_with_by(array, parallelism, partial_compare);
+}
+
+pub fn quick_sort_with_by<T: Send>(
+    array: &mut [T],
+    parallelism: Parallelism,
+    compare: impl Fn(&T, &T) -> Ordering + Sync,
+) {
+    assert!(parallelism.threads > 0, "threads must be at least 1");
+
+    let limit = 2 * (usize::BITS - array.len().leading_zeros());
+    parallel_quick_sort(
+        array,
+        parallelism.threads,
+        parallelism.cutoff,
+        limit,
+        &compare,
+    );
+}
+
+// Sorts the two halves on separate threads while the thread budget lasts,
+// handing half of it to each side, then merges them on the current thread.
+fn parallel_merge_sort<T: Clone + Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    array: &mut [T],
+    buffer: &mut Vec<T>,
+    threads: usize,
+    cutoff: usize,
+    compare: &F,
+) {
+    if array.len() <= usize::max(cutoff, 1) {
+        insertion_sort::sort_by(array, compare);
+        return;
+    }
+
+    let middle = array.len() / 2;
+    let (left, right) = array.split_at_mut(middle);
+
+    if threads > 1 && left.len() >= MIN_PARALLEL_LENGTH {
+        let left_threads = threads / 2;
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut buffer = Vec::with_capacity(left.len() / 2);
+                parallel_merge_sort(left, &mut buffer, left_threads, cutoff, compare);
+            });
+            parallel_merge_sort(right, buffer, threads - left_threads, cutoff, compare);
+        });
+    } else {
+        parallel_merge_sort(left, buffer, 1, cutoff, compare);
+        parallel_merge_sort(right, buffer, 1, cutoff, compare);
+    }
+
+    merge_sort::merge(array, middle, buffer, &mut Probed::new(compare, NoProbe));
+}
+
+// Partitions on the current thread, then sorts the two sides in parallel while
+// the thread budget lasts. Once it runs out this is the sequential quick sort:
+// recurse into the smaller side and loop on the larger. Inputs that defeat the
+// median of three, such as reversed runs, hit the depth limit and are finished
+// with heap sort instead of going quadratic.
+fn parallel_quick_sort<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    mut array: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    mut limit: u32,
+    compare: &F,
+) {
+    let mut ops = Probed::new(compare, NoProbe);
+
+    loop {
+        let length = array.len();
+        if length <= usize::max(cutoff, 1) {
+            insertion_sort::sort_by(array, compare);
+            return;
+        }
+
+        if limit == 0 {
+            heap_sort::sort_by(array, compare);
+            return;
+        }
+        limit -= 1;
+
+        let pivot =
+            quick_sort::median_of_three_with_ops(array, 0, length / 2, length - 1, &mut ops);
+        let middle = quick_sort::hoare_partition_with_ops(array, pivot, &mut ops);
+
+        let (left, rest) = std::mem::take(&mut array).split_at_mut(middle);
+        let right = &mut rest[1..];
+
+        if threads > 1 && length >= MIN_PARALLEL_LENGTH {
+            let left_threads = threads / 2;
+            thread::scope(|scope| {
+                scope.spawn(|| parallel_quick_sort(left, left_threads, cutoff, limit, compare));
+                parallel_quick_sort(right, threads - left_threads, cutoff, limit, compare);
+            });
+            return;
+        }
+
+        if left.len() < right.len() {
+            parallel_quick_sort(left, 1, cutoff, limit, compare);
+            array = right;
+        } else {
+            parallel_quick_sort(right, 1, cutoff, limit, compare);
+            array = left;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Mutex};
+
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    fn variants() -> Vec<fn(&mut [u64], Parallelism)> {
+        vec![merge_sort_with, quick_sort_with]
+    }
+
+    fn assert_every_setting_sorts(array: &[u64]) {
+        let mut expected = array.to_vec();
+        expected.sort();
+
+        for variant in variants() {
+            for threads in [1, 2, 3, 8] {
+                for cutoff in [0, 1, 16, 100] {
+                    let mut actual = array.to_vec();
+                    variant(actual.as_mut_slice(), Parallelism { threads, cutoff });
+
+                    assert_eq!(actual, expected, "threads {threads}, cutoff {cutoff}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_setting_sorts() {
+        assert_every_setting_sorts(&[]);
+        assert_every_setting_sorts(&[1]);
+        assert_every_setting_sorts(&[10, 9, 22, 10, 15, 5, 20, 1, 2, 1]);
+        assert_every_setting_sorts(&(0..20_000).rev().collect::<Vec<u64>>());
+        assert_every_setting_sorts(&pseudo_random(20_000, u64::MAX));
+        assert_every_setting_sorts(&pseudo_random(20_000, 3));
+    }
+
+    // Records which threads the comparator was called on, so a test can tell
+    // whether the sort actually split the work.
+    fn threads_used(
+        array: &[u64],
+        sort: impl Fn(&mut [u64], &(dyn Fn(&u64, &u64) -> Ordering + Sync)),
+    ) -> usize {
+        let threads = Mutex::new(HashSet::new());
+        let mut actual = array.to_vec();
+        sort(actual.as_mut_slice(), &|a, b| {
+            threads.lock().unwrap().insert(thread::current().id());
+            a.cmp(b)
+        });
+
+        let mut expected = array.to_vec();
+        expected.sort();
+        assert_eq!(actual, expected);
+
+        threads.into_inner().unwrap().len()
+    }
+
+    #[test]
+    fn sorts_past_the_parallel_length_use_several_threads() {
+        let parallelism = Parallelism {
+            threads: 4,
+            cutoff: 32,
+        };
+        let merge = |array: &mut [u64], compare: &(dyn Fn(&u64, &u64) -> Ordering + Sync)| {
+            merge_sort_with_by(array, parallelism, compare)
+        };
+        let quick = |array: &mut [u64], compare: &(dyn Fn(&u64, &u64) -> Ordering + Sync)| {
+            quick_sort_with_by(array, parallelism, compare)
+        };
+
+        // Merge sort splits when each half reaches the parallel length, quick
+        // sort when the whole slice does.
+        assert_eq!(
+            threads_used(&pseudo_random(2 * MIN_PARALLEL_LENGTH - 1, 1_000), merge),
+            1
+        );
+        assert!(threads_used(&pseudo_random(2 * MIN_PARALLEL_LENGTH, 1_000), merge) > 1);
+        assert!(threads_used(&pseudo_random(50_000, u64::MAX), merge) > 1);
+
+        assert_eq!(
+            threads_used(&pseudo_random(MIN_PARALLEL_LENGTH - 1, 1_000), quick),
+            1
+        );
+        assert!(threads_used(&pseudo_random(MIN_PARALLEL_LENGTH, 1_000), quick) > 1);
+        assert!(threads_used(&pseudo_random(50_000, u64::MAX), quick) > 1);
+
+        let single = Parallelism {
+            threads: 1,
+            cutoff: 32,
+        };
+        let array = pseudo_random(50_000, u64::MAX);
+        assert_eq!(
+            threads_used(&array, |array, compare| merge_sort_with_by(
+                array, single, compare
+            )),
+            1
+        );
+        assert_eq!(
+            threads_used(&array, |array, compare| quick_sort_with_by(
+                array, single, compare
+            )),
+            1
+        );
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(30_000, 10)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        merge_sort_with_by(
+            array_1.as_mut_slice(),
+            Parallelism {
+                threads: 4,
+                cutoff: 32,
+            },
+            |a, b| a.0.cmp(&b.0),
+        );
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_descending() {
+        let mut array_1 = pseudo_random(20_000, 1_000);
+        let mut array_2 = array_1.clone();
+        let mut array_3 = array_1.clone();
+
+        array_2.sort_by_key(|value| std::cmp::Reverse(*value));
+        merge_sort_by_key(array_1.as_mut_slice(), |value| std::cmp::Reverse(*value));
+        quick_sort_by_key(array_3.as_mut_slice(), |value| std::cmp::Reverse(*value));
+
+        assert_eq!(array_1, array_2);
+        assert_eq!(array_3, array_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "threads must be at least 1")]
+    fn zero_threads_is_rejected() {
+        quick_sort_with(
+            &mut [2, 1],
+            Parallelism {
+                threads: 0,
+                cutoff: 16,
+            },
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+
+use super::{
+    merge, partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    radix_sort::apply_permutation_by,
+    sorter::{Complexity, Sorter},
+};
+use crate::search::binary_search;
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+// Deals the elements onto piles like the card game: each goes on the leftmost
+// pile whose top is greater than it, or starts a new pile on the right. Every
+// pile then reads in order from the top down, and the piles are merged. An
+// element never lands on an equal one, so equal elements spread rightwards
+// across piles in their original order and the merge, which prefers earlier
+// piles on ties, keeps the sort stable. Piles hold indices, so the elements
+// themselves are only moved once, at the end.
+pub(crate) fn sort_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
+    let mut piles: Vec<Vec<usize>> = Vec::new();
+
+    for index in 0..array.len() {
+        let pile = binary_search::upper_bound_by(&piles, |pile| {
+            ops.compare(&array[pile[pile.len() - 1]], &array[index])
+        });
+
+        match piles.get_mut(pile) {
+            Some(pile) => pile.push(index),
+            None => piles.push(vec![index]),
+        }
+    }
+
+    let mut order: Vec<usize> = merge::merge_by(
+        piles.iter().map(|pile| pile.iter().rev().copied()),
+        |a: &usize, b: &usize| ops.compare(&array[*a], &array[*b]),
+    )
+    .collect();
+
+    apply_permutation_by(array, &mut order, |array, i, j| ops.swap(array, i, j));
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PatienceSort;
+
+impl<T> Sorter<T> for PatienceSort {
+    fn name(&self) -> &'static str {
+        "patience_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        false
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
+pub fn longest_increasing_subsequence<T: PartialOrd>(array: &[T]) -> Vec<usize> {
+    longest_increasing_subsequence_by(array, partial_compare)
+}
+
+pub fn longest_increasing_subsequence_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<usize> {
+    longest_increasing_subsequence_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Returns the indices of a longest strictly increasing subsequence, in order.
+pub fn longest_increasing_subsequence_by<T>(
+    array: &[T],
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<usize> {
+    longest_subsequence(array, true, compare)
+}
+
+pub fn longest_non_decreasing_subsequence<T: PartialOrd>(array: &[T]) -> Vec<usize> {
+    longest_non_decreasing_subsequence_by(array, partial_compare)
+}
+
+pub fn longest_non_decreasing_subsequence_by_key<T, K: PartialOrd>(
+    array: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<usize> {
+    longest_non_decreasing_subsequence_by(array, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Returns the indices of a longest subsequence with no descents, in order.
+pub fn longest_non_decreasing_subsequence_by<T>(
+    array: &[T],
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<usize> {
+    longest_subsequence(array, false, compare)
+}
+
+// Deals the elements onto piles as `sort_by` does, but only keeps each pile's
+// top, and has every element remember the top of the pile to its left when it
+// was dealt. That element precedes it in the array and is smaller, so
+// following these links back from the top of the last pile spells out a
+// subsequence with one element per pile, and there can be no longer one. For
+// a strictly increasing subsequence equal elements must not sit side by side,
+// so an element may also go on a pile whose top equals it.
+pub(crate) fn longest_subsequence<T>(
+    array: &[T],
+    strict: bool,
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<usize> {
+    let mut tops: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = Vec::with_capacity(array.len());
+
+    for index in 0..array.len() {
+        let order = |top: &usize| compare(&array[*top], &array[index]);
+        let pile = if strict {
+            binary_search::lower_bound_by(&tops, order)
+        } else {
+            binary_search::upper_bound_by(&tops, order)
+        };
+
+        previous.push(pile.checked_sub(1).map(|left| tops[left]));
+        match tops.get_mut(pile) {
+            Some(top) => *top = index,
+            None => tops.push(index),
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(tops.len());
+    let mut current = tops.last().copied();
+    while let Some(index) = current {
+        subsequence.push(index);
+        current = previous[index];
+    }
+
+    subsequence.reverse();
+    subsequence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::patience;
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Quadratic dynamic programming over every prefix, as a reference for the
+    // length of the longest subsequence.
+    fn brute_force_length(array: &[u64], strict: bool) -> usize {
+        let mut lengths = vec![1; array.len()];
+        for j in 0..array.len() {
+            for i in 0..j {
+                if array[i] < array[j] || (!strict && array[i] == array[j]) {
+                    lengths[j] = usize::max(lengths[j], lengths[i] + 1);
+                }
+            }
+        }
+
+        lengths.into_iter().max().unwrap_or(0)
+    }
+
+    fn assert_subsequence(array: &[u64], indices: &[usize], strict: bool) {
+        assert_eq!(indices.len(), brute_force_length(array, strict));
+        for pair in indices.windows(2) {
+            assert!(pair[0] < pair[1]);
+            if strict {
+                assert!(array[pair[0]] < array[pair[1]]);
+            } else {
+                assert!(array[pair[0]] <= array[pair[1]]);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(2_000, 20)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| std::cmp::Reverse(*key));
+        patience::sort_by_key(array_1.as_mut_slice(), |(key, _)| std::cmp::Reverse(*key));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn subsequences_are_longest_and_ordered() {
+        for length in [0, 1, 2, 17, 300] {
+            for modulus in [3, 40, 1_000] {
+                let array = pseudo_random(length, modulus);
+
+                assert_subsequence(&array, &longest_increasing_subsequence(&array), true);
+                assert_subsequence(&array, &longest_non_decreasing_subsequence(&array), false);
+            }
+        }
+    }
+
+    #[test]
+    fn subsequence_of_known_sequence() {
+        let array = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+        let indices = longest_increasing_subsequence(&array);
+        let values: Vec<i32> = indices.iter().map(|index| array[*index]).collect();
+
+        assert_eq!(values, [0, 2, 6, 9, 11, 15]);
+        assert_eq!(indices, [0, 4, 6, 9, 13, 15]);
+        assert_eq!(longest_non_decreasing_subsequence(&[2, 2, 1, 2]), [0, 1, 3]);
+        assert_eq!(longest_increasing_subsequence(&[2, 2, 1, 2]).len(), 2);
+    }
+
+    #[test]
+    fn subsequence_by_comparator_and_key() {
+        let array = [5, 1, 4, 4, 2, 3, 0];
+
+        assert_eq!(
+            longest_increasing_subsequence_by(&array, |a, b| b.cmp(a)),
+            [0, 3, 5, 6]
+        );
+        assert_eq!(
+            longest_non_decreasing_subsequence_by(&array, |a, b| b.cmp(a)),
+            [0, 2, 3, 5, 6]
+        );
+
+        let lines = ["b", "a", "cc", "dd", "e", "fff"];
+        assert_eq!(
+            longest_increasing_subsequence_by_key(&lines, |line| line.len()),
+            [1, 3, 5]
+        );
+        assert_eq!(
+            longest_non_decreasing_subsequence_by_key(&lines, |line| line.len()),
+            [0, 1, 2, 3, 5]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+// Observes the primitive operations a sort performs. Every method is called
+// after the operation has happened, with the slice the algorithm was working
+// on at the time; recursive sorts pass sub-slices, so indices are relative to
+// the slice that is passed in.
+pub trait Probe<T> {
+    fn compare(&mut self, _a: &T, _b: &T) {}
+
+    fn swap(&mut self, _array: &[T], _i: usize, _j: usize) {}
+
+    // `array[index]` was overwritten with a value from outside the slice, such
+    // as an element held in a scratch buffer.
+    fn write(&mut self, _array: &[T], _index: usize) {}
+
+    // `elements` values were copied into auxiliary storage.
+    fn allocate(&mut self, _elements: usize) {}
+
+    // `elements` values held in auxiliary storage were dropped.
+    fn release(&mut self, _elements: usize) {}
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProbe;
+
+impl<T> Probe<T> for NoProbe {}
+
+impl<T, P: Probe<T> + ?Sized> Probe<T> for &mut P {
+    fn compare(&mut self, a: &T, b: &T) {
+        (**self).compare(a, b);
+    }
+
+    fn swap(&mut self, array: &[T], i: usize, j: usize) {
+        (**self).swap(array, i, j);
+    }
+
+    fn write(&mut self, array: &[T], index: usize) {
+        (**self).write(array, index);
+    }
+
+    fn allocate(&mut self, elements: usize) {
+        (**self).allocate(elements);
+    }
+
+    fn release(&mut self, elements: usize) {
+        (**self).release(elements);
+    }
+}
+
+// The operations sorts are written against, so that a single implementation
+// serves both plain sorting (with `NoProbe`, which compiles away) and
+// instrumented runs.
+pub(crate) trait SortOps<T> {
+    fn compare(&mut self, a: &T, b: &T) -> Ordering;
+
+    fn swap(&mut self, array: &mut [T], i: usize, j: usize);
+
+    fn write(&mut self, array: &mut [T], index: usize, value: T);
+
+    // Writes the element in `hand` into the slice like `write`, leaving the
+    // element it displaced in `hand`.
+    fn replace(&mut self, array: &mut [T], index: usize, hand: &mut T);
+
+    fn allocate(&mut self, elements: usize);
+
+    fn release(&mut self, elements: usize);
+
+    fn is_less(&mut self, a: &T, b: &T) -> bool {
+        self.compare(a, b).is_lt()
+    }
+
+    fn reverse(&mut self, array: &mut [T]);
+
+    // Moves the last element to the front, shifting the rest up by one.
+    fn rotate_right(&mut self, array: &mut [T]);
+
+    // Replaces the contents of the scratch buffer with clones of `values`.
+    fn fill_buffer(&mut self, buffer: &mut Vec<T>, values: &[T])
+    where
+        T: Clone,
+    {
+        self.clear_buffer(buffer);
+        buffer.extend_from_slice(values);
+        self.allocate(values.len());
+    }
+
+    fn clear_buffer(&mut self, buffer: &mut Vec<T>) {
+        self.release(buffer.len());
+        buffer.clear();
+    }
+}
+
+pub(crate) struct Probed<F, P> {
+    compare: F,
+    probe: P,
+}
+
+impl<F, P> Probed<F, P> {
+    pub(crate) fn new(compare: F, probe: P) -> Self {
+        Self { compare, probe }
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> Ordering, P: Probe<T>> SortOps<T> for Probed<F, P> {
+    fn compare(&mut self, a: &T, b: &T) -> Ordering {
+        self.probe.compare(a, b);
+        (self.compare)(a, b)
+    }
+
+    fn swap(&mut self, array: &mut [T], i: usize, j: usize) {
+        array.swap(i, j);
+        self.probe.swap(array, i, j);
+    }
+
+    fn write(&mut self, array: &mut [T], index: usize, value: T) {
+        array[index] = value;
+        self.probe.write(array, index);
+    }
+
+    fn replace(&mut self, array: &mut [T], index: usize, hand: &mut T) {
+        std::mem::swap(&mut array[index], hand);
+        self.probe.write(array, index);
+    }
+
+    fn allocate(&mut self, elements: usize) {
+        self.probe.allocate(elements);
+    }
+
+    fn release(&mut self, elements: usize) {
+        self.probe.release(elements);
+    }
+
+    // Bulk moves run at full speed and are reported afterwards as the sequence
+    // of swaps that has the same effect.
+    fn reverse(&mut self, array: &mut [T]) {
+        array.reverse();
+
+        let length = array.len();
+        for i in 0..length / 2 {
+            self.probe.swap(array, i, length - 1 - i);
+        }
+    }
+
+    fn rotate_right(&mut self, array: &mut [T]) {
+        array.rotate_right(1);
+
+        for i in (1..array.len()).rev() {
+            self.probe.swap(array, i - 1, i);
+        }
+    }
+}
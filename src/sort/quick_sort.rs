@@ -0,0 +1,471 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    random::XorShift,
+    sorter::{Complexity, Sorter},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Partition {
+    Lomuto,
+    Hoare,
+    ThreeWay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    First,
+    Last,
+    MedianOfThree,
+    Ninther,
+    Random,
+}
+
+impl Partition {
+    pub const ALL: [Partition; 3] = [Partition::Lomuto, Partition::Hoare, Partition::ThreeWay];
+}
+
+impl Pivot {
+    pub const ALL: [Pivot; 5] = [
+        Pivot::First,
+        Pivot::Last,
+        Pivot::MedianOfThree,
+        Pivot::Ninther,
+        Pivot::Random,
+    ];
+}
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_by(array, Partition::Hoare, Pivot::MedianOfThree, compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_with<T: PartialOrd>(array: &mut [T], partition: Partition, pivot: Pivot) {
+    sort_with_by(array, partition, pivot, partial_compare);
+}
+
+pub fn sort_with_by<T>(
+    array: &mut [T],
+    partition: Partition,
+    pivot: Pivot,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    let mut rng = XorShift::new();
+    quick_sort(
+        array,
+        partition,
+        pivot,
+        &mut rng,
+        &mut Probed::new(compare, NoProbe),
+    );
+}
+
+pub fn sort_lomuto<T: PartialOrd>(array: &mut [T], pivot: Pivot) {
+    sort_with(array, Partition::Lomuto, pivot);
+}
+
+pub fn sort_hoare<T: PartialOrd>(array: &mut [T], pivot: Pivot) {
+    sort_with(array, Partition::Hoare, pivot);
+}
+
+pub fn sort_three_way<T: PartialOrd>(array: &mut [T], pivot: Pivot) {
+    sort_with(array, Partition::ThreeWay, pivot);
+}
+
+fn quick_sort<T>(
+    mut array: &mut [T],
+    partition: Partition,
+    pivot: Pivot,
+    rng: &mut XorShift,
+    ops: &mut impl SortOps<T>,
+) {
+    // Recursing into the smaller side and looping on the larger one keeps the
+    // stack depth logarithmic even when a pivot strategy degrades to O(n^2).
+    while array.len() > 1 {
+        let pivot_index = select_pivot(array, pivot, rng, ops);
+        let (left_end, right_start) = match partition {
+            Partition::Lomuto => {
+                let index = lomuto_partition_with_ops(array, pivot_index, ops);
+                (index, index + 1)
+            }
+            Partition::Hoare => {
+                let index = hoare_partition_with_ops(array, pivot_index, ops);
+                (index, index + 1)
+            }
+            Partition::ThreeWay => three_way_partition_with_ops(array, pivot_index, ops),
+        };
+
+        let (left, rest) = std::mem::take(&mut array).split_at_mut(left_end);
+        let right = &mut rest[right_start - left_end..];
+
+        if left.len() < right.len() {
+            quick_sort(left, partition, pivot, rng, ops);
+            array = right;
+        } else {
+            quick_sort(right, partition, pivot, rng, ops);
+            array = left;
+        }
+    }
+}
+
+pub(crate) fn select_pivot<T>(
+    array: &[T],
+    pivot: Pivot,
+    rng: &mut XorShift,
+    ops: &mut impl SortOps<T>,
+) -> usize {
+    let length = array.len();
+    let mut median = |a, b, c| median_of_three_with_ops(array, a, b, c, ops);
+
+    match pivot {
+        Pivot::First => 0,
+        Pivot::Last => length - 1,
+        Pivot::MedianOfThree => median(0, length / 2, length - 1),
+        Pivot::Ninther if length < 9 => median(0, length / 2, length - 1),
+        Pivot::Ninther => {
+            let step = length / 8;
+            let middle = length / 2;
+            let last = length - 1;
+
+            let first_median = median(0, step, 2 * step);
+            let middle_median = median(middle - step, middle, middle + step);
+            let last_median = median(last - 2 * step, last - step, last);
+            median(first_median, middle_median, last_median)
+        }
+        Pivot::Random => rng.below(length),
+    }
+}
+
+pub fn median_of_three<T: PartialOrd>(array: &[T], a: usize, b: usize, c: usize) -> usize {
+    median_of_three_by(array, a, b, c, partial_compare)
+}
+
+pub fn median_of_three_by<T>(
+    array: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> usize {
+    median_of_three_with_ops(array, a, b, c, &mut Probed::new(compare, NoProbe))
+}
+
+pub(crate) fn median_of_three_with_ops<T>(
+    array: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    ops: &mut impl SortOps<T>,
+) -> usize {
+    let mut is_less = |x: usize, y: usize| ops.is_less(&array[x], &array[y]);
+
+    if is_less(a, b) {
+        if is_less(b, c) {
+            b
+        } else if is_less(a, c) {
+            c
+        } else {
+            a
+        }
+    } else if is_less(a, c) {
+        a
+    } else if is_less(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+pub fn lomuto_partition<T: PartialOrd>(array: &mut [T], pivot: usize) -> usize {
+    lomuto_partition_by(array, pivot, partial_compare)
+}
+
+// Moves the pivot to the end, gathers everything smaller in front of it and
+// returns the pivot's final index.
+pub fn lomuto_partition_by<T>(
+    array: &mut [T],
+    pivot: usize,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> usize {
+    lomuto_partition_with_ops(array, pivot, &mut Probed::new(compare, NoProbe))
+}
+
+pub(crate) fn lomuto_partition_with_ops<T>(
+    array: &mut [T],
+    pivot: usize,
+    ops: &mut impl SortOps<T>,
+) -> usize {
+    let last = array.len() - 1;
+    ops.swap(array, pivot, last);
+
+    let mut store = 0;
+    for i in 0..last {
+        if ops.compare(&array[i], &array[last]).is_lt() {
+            ops.swap(array, i, store);
+            store += 1;
+        }
+    }
+
+    ops.swap(array, store, last);
+    store
+}
+
+pub fn hoare_partition<T: PartialOrd>(array: &mut [T], pivot: usize) -> usize {
+    hoare_partition_by(array, pivot, partial_compare)
+}
+
+// Moves the pivot to the front and scans inwards from both ends, stopping on
+// keys equal to the pivot so runs of duplicates are split evenly. Returns the
+// pivot's final index.
+pub fn hoare_partition_by<T>(
+    array: &mut [T],
+    pivot: usize,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> usize {
+    hoare_partition_with_ops(array, pivot, &mut Probed::new(compare, NoProbe))
+}
+
+pub(crate) fn hoare_partition_with_ops<T>(
+    array: &mut [T],
+    pivot: usize,
+    ops: &mut impl SortOps<T>,
+) -> usize {
+    ops.swap(array, 0, pivot);
+
+    let mut i = 1;
+    let mut j = array.len() - 1;
+
+    loop {
+        while i <= j && ops.compare(&array[i], &array[0]).is_lt() {
+            i += 1;
+        }
+        while i <= j && ops.compare(&array[0], &array[j]).is_lt() {
+            j -= 1;
+        }
+
+        if i >= j {
+            break;
+        }
+
+        ops.swap(array, i, j);
+        i += 1;
+        j -= 1;
+    }
+
+    ops.swap(array, 0, j);
+    j
+}
+
+pub fn three_way_partition<T: PartialOrd>(array: &mut [T], pivot: usize) -> (usize, usize) {
+    three_way_partition_by(array, pivot, partial_compare)
+}
+
+// Dutch national flag partition. Returns `(lt, gt)` such that `array[..lt]` is
+// less than the pivot, `array[lt..gt]` is equal to it and `array[gt..]` is
+// greater.
+pub fn three_way_partition_by<T>(
+    array: &mut [T],
+    pivot: usize,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) -> (usize, usize) {
+    three_way_partition_with_ops(array, pivot, &mut Probed::new(compare, NoProbe))
+}
+
+pub(crate) fn three_way_partition_with_ops<T>(
+    array: &mut [T],
+    pivot: usize,
+    ops: &mut impl SortOps<T>,
+) -> (usize, usize) {
+    ops.swap(array, 0, pivot);
+
+    let mut lt = 0;
+    let mut i = 1;
+    let mut gt = array.len();
+
+    while i < gt {
+        match ops.compare(&array[i], &array[lt]) {
+            Ordering::Less => {
+                ops.swap(array, lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                ops.swap(array, i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+
+    (lt, gt)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuickSort {
+    pub partition: Partition,
+    pub pivot: Pivot,
+}
+
+impl Default for QuickSort {
+    fn default() -> Self {
+        Self {
+            partition: Partition::Hoare,
+            pivot: Pivot::MedianOfThree,
+        }
+    }
+}
+
+impl<T> Sorter<T> for QuickSort {
+    fn name(&self) -> &'static str {
+        "quick_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_with_by(array, self.partition, self.pivot, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        let mut rng = XorShift::new();
+        quick_sort(
+            array,
+            self.partition,
+            self.pivot,
+            &mut rng,
+            &mut Probed::new(compare, probe),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::quick_sort::{self, Partition, Pivot};
+    use crate::testing::pseudo_random;
+
+    fn assert_every_strategy_sorts(array: &[u64]) {
+        let mut expected = array.to_vec();
+        expected.sort();
+
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                let mut actual = array.to_vec();
+                quick_sort::sort_with(actual.as_mut_slice(), partition, pivot);
+
+                assert_eq!(actual, expected, "{partition:?} with {pivot:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_strategy_sorts_small_arrays() {
+        assert_every_strategy_sorts(&[]);
+        assert_every_strategy_sorts(&[1]);
+        assert_every_strategy_sorts(&[2, 1]);
+        assert_every_strategy_sorts(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_every_strategy_sorts(&[10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_every_strategy_sorts(&[10, 9, 22, 10, 15, 5, 20, 1, 2, 1]);
+    }
+
+    #[test]
+    fn every_strategy_sorts_adversarial_arrays() {
+        let sorted: Vec<u64> = (0..2_000).collect();
+        let backwards: Vec<u64> = (0..2_000).rev().collect();
+        let organ_pipe: Vec<u64> = (0..1_000).chain((0..1_000).rev()).collect();
+
+        assert_every_strategy_sorts(&sorted);
+        assert_every_strategy_sorts(&backwards);
+        assert_every_strategy_sorts(&organ_pipe);
+    }
+
+    #[test]
+    fn every_strategy_sorts_duplicate_heavy_arrays() {
+        assert_every_strategy_sorts(&[7; 1_000]);
+        assert_every_strategy_sorts(&pseudo_random(2_000, 3));
+    }
+
+    #[test]
+    fn every_strategy_sorts_random_arrays() {
+        assert_every_strategy_sorts(&pseudo_random(5_000, u64::MAX));
+    }
+
+    #[test]
+    fn three_way_partition_groups_equal_keys() {
+        let mut array = vec![3, 1, 3, 5, 3, 0, 3, 9];
+
+        let (lt, gt) = quick_sort::three_way_partition(array.as_mut_slice(), 0);
+
+        assert!(array[..lt].iter().all(|value| *value < 3));
+        assert!(array[lt..gt].iter().all(|value| *value == 3));
+        assert!(array[gt..].iter().all(|value| *value > 3));
+        assert_eq!(gt - lt, 4);
+    }
+
+    #[test]
+    fn lomuto_and_hoare_place_pivot_at_returned_index() {
+        let array = vec![6, 2, 9, 4, 6, 1, 8, 3];
+
+        for partition in [quick_sort::lomuto_partition, quick_sort::hoare_partition] {
+            let mut array = array.clone();
+
+            let index = partition(array.as_mut_slice(), 0);
+
+            assert_eq!(array[index], 6);
+            assert!(array[..index].iter().all(|value| *value <= 6));
+            assert!(array[index + 1..].iter().all(|value| *value >= 6));
+        }
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let array = pseudo_random(1_000, 50);
+        let mut expected = array.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                let mut actual = array.clone();
+                quick_sort::sort_with_by(actual.as_mut_slice(), partition, pivot, |a, b| b.cmp(a));
+
+                assert_eq!(actual, expected, "{partition:?} with {pivot:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (4, "d"), (2, "b")];
+
+        quick_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+    }
+}
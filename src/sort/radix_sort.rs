@@ -0,0 +1,384 @@
+use super::insertion_sort;
+
+const DEFAULT_RADIX_BITS: u32 = 8;
+const MAX_RADIX_BITS: u32 = 16;
+const MSD_RADIX_BITS: u32 = 8;
+const MSD_INSERTION_THRESHOLD: usize = 32;
+
+// Maps a value to an unsigned key whose natural order matches the value's
+// order. Only the low `BITS` bits of the key may be set.
+pub trait RadixKey {
+    const BITS: u32;
+
+    fn radix_key(&self) -> u128;
+}
+
+macro_rules! impl_radix_key_for_unsigned {
+    ($($unsigned:ty),*) => {
+        $(
+            impl RadixKey for $unsigned {
+                const BITS: u32 = <$unsigned>::BITS;
+
+                fn radix_key(&self) -> u128 {
+                    *self as u128
+                }
+            }
+        )*
+    };
+}
+
+// Flipping the sign bit maps `MIN..=MAX` onto `0..=unsigned MAX` in order, so
+// negative values sort before positive ones.
+macro_rules! impl_radix_key_for_signed {
+    ($($signed:ty => $unsigned:ty),*) => {
+        $(
+            impl RadixKey for $signed {
+                const BITS: u32 = <$signed>::BITS;
+
+                fn radix_key(&self) -> u128 {
+                    (*self as $unsigned ^ (1 << (<$signed>::BITS - 1))) as u128
+                }
+            }
+        )*
+    };
+}
+
+// Orders floats the way `total_cmp` does: negative values have every bit
+// flipped, so larger magnitudes come first, and positive values just have the
+// sign bit set. NaNs land at either end according to their sign.
+macro_rules! impl_radix_key_for_float {
+    ($($float:ty => $unsigned:ty),*) => {
+        $(
+            impl RadixKey for $float {
+                const BITS: u32 = <$unsigned>::BITS;
+
+                fn radix_key(&self) -> u128 {
+                    let bits = self.to_bits();
+                    let sign = 1 << (<$unsigned>::BITS - 1);
+
+                    (if bits & sign == 0 { bits ^ sign } else { !bits }) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key_for_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_for_signed!(
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize
+);
+impl_radix_key_for_float!(f32 => u32, f64 => u64);
+
+pub fn sort<T: RadixKey>(array: &mut [T]) {
+    sort_lsd(array, DEFAULT_RADIX_BITS);
+}
+
+pub fn sort_lsd<T: RadixKey>(array: &mut [T], radix_bits: u32) {
+    lsd(array, radix_bits, T::BITS, |value| value.radix_key());
+}
+
+pub fn sort_lsd_by_key<T, K: RadixKey>(
+    array: &mut [T],
+    radix_bits: u32,
+    mut key: impl FnMut(&T) -> K,
+) {
+    lsd(array, radix_bits, K::BITS, |value| key(value).radix_key());
+}
+
+pub fn sort_msd<T: RadixKey>(array: &mut [T]) {
+    msd(array, T::BITS, |value| value.radix_key());
+}
+
+// Unlike the LSD variant this is not stable: buckets are filled in place by
+// swapping, American flag style.
+pub fn sort_msd_by_key<T, K: RadixKey>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    msd(array, K::BITS, |value| key(value).radix_key());
+}
+
+fn lsd<T>(array: &mut [T], radix_bits: u32, key_bits: u32, key: impl FnMut(&T) -> u128) {
+    assert!(
+        (1..=MAX_RADIX_BITS).contains(&radix_bits),
+        "radix bits must be between 1 and {MAX_RADIX_BITS}, got {radix_bits}"
+    );
+
+    let entries = keyed_entries(array, key);
+    let mut order = lsd_order(entries, radix_bits, key_bits);
+    apply_permutation(array, &mut order);
+}
+
+fn msd<T>(array: &mut [T], key_bits: u32, key: impl FnMut(&T) -> u128) {
+    let mut entries = keyed_entries(array, key);
+
+    if key_bits > 0 {
+        let top_shift = (key_bits - 1) / MSD_RADIX_BITS * MSD_RADIX_BITS;
+        msd_entries(&mut entries, top_shift);
+    }
+
+    let mut order: Vec<usize> = entries.into_iter().map(|(_, index)| index).collect();
+    apply_permutation(array, &mut order);
+}
+
+// Keys are computed once up front and sorted alongside the element's original
+// index, so the key function never runs more than once per element and the
+// elements themselves are moved exactly once at the end.
+fn keyed_entries<T>(array: &[T], mut key: impl FnMut(&T) -> u128) -> Vec<(u128, usize)> {
+    array
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (key(value), index))
+        .collect()
+}
+
+fn digit(key: u128, shift: u32, mask: u128) -> usize {
+    ((key >> shift) & mask) as usize
+}
+
+fn lsd_order(mut entries: Vec<(u128, usize)>, radix_bits: u32, key_bits: u32) -> Vec<usize> {
+    let buckets = 1 << radix_bits;
+    let mask = (buckets - 1) as u128;
+    let mut scratch = entries.clone();
+    let mut counts = vec![0; buckets + 1];
+
+    let mut shift = 0;
+    while shift < key_bits {
+        counts.fill(0);
+        for (key, _) in &entries {
+            counts[digit(*key, shift, mask) + 1] += 1;
+        }
+
+        // A pass where every key shares the same digit would not move anything.
+        if !counts.contains(&entries.len()) {
+            for bucket in 1..counts.len() {
+                counts[bucket] += counts[bucket - 1];
+            }
+
+            for entry in &entries {
+                let bucket = digit(entry.0, shift, mask);
+                scratch[counts[bucket]] = *entry;
+                counts[bucket] += 1;
+            }
+
+            std::mem::swap(&mut entries, &mut scratch);
+        }
+
+        shift += radix_bits;
+    }
+
+    entries.into_iter().map(|(_, index)| index).collect()
+}
+
+fn msd_entries(entries: &mut [(u128, usize)], shift: u32) {
+    if entries.len() <= MSD_INSERTION_THRESHOLD {
+        insertion_sort::sort(entries);
+        return;
+    }
+
+    let buckets = 1 << MSD_RADIX_BITS;
+    let mask = (buckets - 1) as u128;
+
+    let mut ends = vec![0; buckets];
+    for (key, _) in entries.iter() {
+        ends[digit(*key, shift, mask)] += 1;
+    }
+    for bucket in 1..buckets {
+        ends[bucket] += ends[bucket - 1];
+    }
+
+    let mut next: Vec<usize> = (0..buckets)
+        .map(|bucket| if bucket == 0 { 0 } else { ends[bucket - 1] })
+        .collect();
+    let starts = next.clone();
+
+    for bucket in 0..buckets {
+        while next[bucket] < ends[bucket] {
+            let target = digit(entries[next[bucket]].0, shift, mask);
+            if target == bucket {
+                next[bucket] += 1;
+            } else {
+                entries.swap(next[bucket], next[target]);
+                next[target] += 1;
+            }
+        }
+    }
+
+    if shift == 0 {
+        return;
+    }
+
+    for bucket in 0..buckets {
+        if ends[bucket] - starts[bucket] > 1 {
+            msd_entries(
+                &mut entries[starts[bucket]..ends[bucket]],
+                shift - MSD_RADIX_BITS,
+            );
+        }
+    }
+}
+
+// Reorders the slice so that position `i` ends up holding the element that was
+// at `order[i]`, following each cycle of the permutation with swaps. `order` is
+// consumed in the process.
+pub(crate) fn apply_permutation<T>(array: &mut [T], order: &mut [usize]) {
+    apply_permutation_by(array, order, <[T]>::swap);
+}
+
+// Like `apply_permutation`, but every swap goes through `swap`, so that sorts
+// built on a permutation can report them.
+pub(crate) fn apply_permutation_by<T>(
+    array: &mut [T],
+    order: &mut [usize],
+    mut swap: impl FnMut(&mut [T], usize, usize),
+) {
+    for start in 0..order.len() {
+        let mut current = start;
+
+        while order[current] != start {
+            let next = order[current];
+            swap(array, current, next);
+            order[current] = current;
+            current = next;
+        }
+
+        order[current] = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::radix_sort::{self, RadixKey};
+    use crate::testing::pseudo_random;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Record {
+        id: u32,
+        name: &'static str,
+    }
+
+    impl RadixKey for Record {
+        const BITS: u32 = u32::BITS;
+
+        fn radix_key(&self) -> u128 {
+            self.id.radix_key()
+        }
+    }
+
+    fn assert_sorts<T: RadixKey + Ord + Clone + std::fmt::Debug>(array: Vec<T>) {
+        let mut expected = array.clone();
+        expected.sort();
+
+        let mut lsd = array.clone();
+        radix_sort::sort(lsd.as_mut_slice());
+        assert_eq!(lsd, expected);
+
+        let mut msd = array;
+        radix_sort::sort_msd(msd.as_mut_slice());
+        assert_eq!(msd, expected);
+    }
+
+    #[test]
+    fn sort_empty_and_single_element_arrays() {
+        assert_sorts(Vec::<u64>::new());
+        assert_sorts(vec![1u64]);
+    }
+
+    #[test]
+    fn sort_large_unsigned_arrays() {
+        let values = pseudo_random(50_000, u64::MAX);
+
+        assert_sorts(values.clone());
+        assert_sorts(values.iter().map(|value| *value as u32).collect());
+        assert_sorts(values.iter().map(|value| *value as u16).collect());
+        assert_sorts(values.iter().map(|value| *value as u128 * 3).collect());
+    }
+
+    #[test]
+    fn sort_signed_arrays_with_negative_values() {
+        let values = pseudo_random(50_000, u64::MAX);
+
+        assert_sorts(values.iter().map(|value| *value as i64).collect());
+        assert_sorts(values.iter().map(|value| *value as i32).collect());
+        assert_sorts(values.iter().map(|value| *value as i8).collect());
+        assert_sorts(values.iter().map(|value| *value as i128).collect());
+        assert_sorts(vec![
+            i64::MAX,
+            0,
+            -1,
+            i64::MIN,
+            1,
+            i64::MIN + 1,
+            i64::MAX - 1,
+        ]);
+        assert_sorts(vec![i8::MAX, 0, -1, i8::MIN, 1]);
+    }
+
+    #[test]
+    fn lsd_sorts_with_any_radix_bits() {
+        let array: Vec<i64> = pseudo_random(5_000, u64::MAX)
+            .into_iter()
+            .map(|value| value as i64)
+            .collect();
+        let mut expected = array.clone();
+        expected.sort();
+
+        for radix_bits in 1..=16 {
+            let mut actual = array.clone();
+            radix_sort::sort_lsd(actual.as_mut_slice(), radix_bits);
+
+            assert_eq!(actual, expected, "radix bits {radix_bits}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "radix bits must be between 1 and 16")]
+    fn lsd_rejects_zero_radix_bits() {
+        radix_sort::sort_lsd(&mut [3u8, 2, 1], 0);
+    }
+
+    #[test]
+    fn lsd_is_stable() {
+        let mut array: Vec<(u8, usize)> = pseudo_random(5_000, u64::MAX)
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (value as u8 % 16, index))
+            .collect();
+        let mut expected = array.clone();
+        expected.sort_by_key(|(key, _)| *key);
+
+        radix_sort::sort_lsd_by_key(array.as_mut_slice(), 4, |(key, _)| *key);
+
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn sort_user_types_by_implemented_key() {
+        let mut array = vec![
+            Record { id: 30, name: "c" },
+            Record { id: 10, name: "a" },
+            Record { id: 20, name: "b" },
+        ];
+
+        radix_sort::sort(array.as_mut_slice());
+
+        let names: Vec<&str> = array.iter().map(|record| record.name).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn sort_user_types_by_projected_key() {
+        let mut array = vec![
+            Record { id: 30, name: "c" },
+            Record { id: 10, name: "a" },
+            Record { id: 20, name: "b" },
+        ];
+
+        radix_sort::sort_msd_by_key(array.as_mut_slice(), |record| -(record.id as i64));
+
+        let names: Vec<&str> = array.iter().map(|record| record.name).collect();
+        assert_eq!(names, vec!["c", "b", "a"]);
+    }
+}
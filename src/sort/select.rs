@@ -0,0 +1,321 @@
+use std::cmp::Ordering;
+
+use super::{
+    heap_sort, insertion_sort, intro_sort, partial_compare,
+    probe::{NoProbe, Probed, SortOps},
+    quick_sort,
+};
+
+const INSERTION_THRESHOLD: usize = 16;
+
+pub fn nth_element<T: PartialOrd>(array: &mut [T], n: usize) {
+    nth_element_by(array, n, partial_compare);
+}
+
+pub fn nth_element_by_key<T, K: PartialOrd>(
+    array: &mut [T],
+    n: usize,
+    mut key: impl FnMut(&T) -> K,
+) {
+    nth_element_by(array, n, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+// Moves the element that would be at index `n` after sorting into place, with
+// nothing greater before it and nothing less after it. Quickselect with a
+// median of three pivot, switching to median of medians pivots if partitions
+// keep coming out unbalanced, so the worst case stays linear.
+pub fn nth_element_by<T>(array: &mut [T], n: usize, compare: impl FnMut(&T, &T) -> Ordering) {
+    assert!(
+        n < array.len(),
+        "index {n} is out of bounds for a slice of length {}",
+        array.len()
+    );
+
+    let limit = 2 * (usize::BITS - array.len().leading_zeros());
+    introselect(array, n, limit, &mut Probed::new(compare, NoProbe));
+}
+
+pub fn partial_sort<T: PartialOrd>(array: &mut [T], k: usize) {
+    partial_sort_by(array, k, partial_compare);
+}
+
+pub fn partial_sort_by_key<T, K: PartialOrd>(
+    array: &mut [T],
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) {
+    partial_sort_by(array, k, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+// Leaves the `k` smallest elements sorted at the front of the slice and the
+// rest in no particular order. A `k` past the end sorts the whole slice.
+pub fn partial_sort_by<T>(array: &mut [T], k: usize, compare: impl FnMut(&T, &T) -> Ordering) {
+    let mut ops = Probed::new(compare, NoProbe);
+
+    if k < array.len() && k > 0 {
+        let limit = 2 * (usize::BITS - array.len().leading_zeros());
+        introselect(array, k - 1, limit, &mut ops);
+    }
+
+    let k = usize::min(k, array.len());
+    intro_sort::sort_with_ops(&mut array[..k], &mut ops);
+}
+
+pub fn top_k<T: PartialOrd>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    top_k_by(items, k, partial_compare)
+}
+
+pub fn top_k_by_key<T, K: PartialOrd>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    top_k_by(items, k, |a, b| partial_compare(&key(a), &key(b)))
+}
+
+// Returns the `k` smallest items in order, the same ones `partial_sort` would
+// leave at the front, holding no more than `k` items at a time: a max-heap of
+// the best seen so far, whose root is replaced whenever something beats it.
+pub fn top_k_by<T>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // `k` may be far more than there are items, so the heap only reserves
+    // what the iterator promises and grows past that as items arrive.
+    let items = items.into_iter();
+    let mut heap = Vec::with_capacity(usize::min(k, items.size_hint().0));
+
+    for item in items {
+        if heap.len() < k {
+            let last = heap.len();
+            heap.push(item);
+            heap_sort::sift_up_by(&mut heap, last, &mut compare);
+        } else if compare(&item, &heap[0]).is_lt() {
+            heap[0] = item;
+            heap_sort::sift_down_by(&mut heap, 0, &mut compare);
+        }
+    }
+
+    intro_sort::sort_by(&mut heap, compare);
+    heap
+}
+
+fn introselect<T>(mut array: &mut [T], mut n: usize, mut limit: u32, ops: &mut impl SortOps<T>) {
+    loop {
+        let length = array.len();
+        if length <= INSERTION_THRESHOLD {
+            insertion_sort::sort_with_ops(array, ops);
+            return;
+        }
+
+        if limit == 0 {
+            median_of_medians_select(array, n, ops);
+            return;
+        }
+        limit -= 1;
+
+        let pivot = quick_sort::median_of_three_with_ops(array, 0, length / 2, length - 1, ops);
+        let middle = quick_sort::hoare_partition_with_ops(array, pivot, ops);
+
+        match n.cmp(&middle) {
+            Ordering::Equal => return,
+            Ordering::Less => array = &mut std::mem::take(&mut array)[..middle],
+            Ordering::Greater => {
+                array = &mut std::mem::take(&mut array)[middle + 1..];
+                n -= middle + 1;
+            }
+        }
+    }
+}
+
+// Deterministic linear-time selection: partitioning around a median of
+// medians of five always discards at least 30% of the slice.
+fn median_of_medians_select<T>(mut array: &mut [T], mut n: usize, ops: &mut impl SortOps<T>) {
+    loop {
+        let length = array.len();
+        if length <= 5 {
+            insertion_sort::sort_with_ops(array, ops);
+            return;
+        }
+
+        let pivot = median_of_medians(array, ops);
+        let middle = quick_sort::hoare_partition_with_ops(array, pivot, ops);
+
+        match n.cmp(&middle) {
+            Ordering::Equal => return,
+            Ordering::Less => array = &mut std::mem::take(&mut array)[..middle],
+            Ordering::Greater => {
+                array = &mut std::mem::take(&mut array)[middle + 1..];
+                n -= middle + 1;
+            }
+        }
+    }
+}
+
+// Sorts each full group of five, gathers the group medians at the front of the
+// slice and selects their median. Returns its index.
+fn median_of_medians<T>(array: &mut [T], ops: &mut impl SortOps<T>) -> usize {
+    let groups = array.len() / 5;
+
+    for group in 0..groups {
+        let start = group * 5;
+        insertion_sort::sort_with_ops(&mut array[start..start + 5], ops);
+        ops.swap(array, group, start + 2);
+    }
+
+    median_of_medians_select(&mut array[..groups], groups / 2, ops);
+    groups / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    fn assert_selected(array: &[u64], n: usize, sorted: &[u64]) {
+        assert_eq!(array[n], sorted[n], "n = {n}");
+        assert!(array[..n].iter().all(|value| *value <= array[n]));
+        assert!(array[n + 1..].iter().all(|value| *value >= array[n]));
+    }
+
+    fn assert_selects_every_index(array: &[u64]) {
+        let mut sorted = array.to_vec();
+        sorted.sort();
+
+        for n in 0..array.len() {
+            let mut actual = array.to_vec();
+            nth_element(actual.as_mut_slice(), n);
+            assert_selected(&actual, n, &sorted);
+
+            let mut actual = array.to_vec();
+            median_of_medians_select(
+                actual.as_mut_slice(),
+                n,
+                &mut Probed::new(partial_compare, NoProbe),
+            );
+            assert_selected(&actual, n, &sorted);
+        }
+    }
+
+    #[test]
+    fn nth_element_selects_every_index() {
+        assert_selects_every_index(&[1]);
+        assert_selects_every_index(&[10, 9, 22, 10, 15, 5, 20, 1, 2, 1]);
+        assert_selects_every_index(&(0..200).collect::<Vec<u64>>());
+        assert_selects_every_index(&(0..200).rev().collect::<Vec<u64>>());
+        assert_selects_every_index(&pseudo_random(300, 1_000));
+        assert_selects_every_index(&pseudo_random(300, 3));
+        assert_selects_every_index(&[4; 50]);
+    }
+
+    #[test]
+    fn nth_element_finds_median_of_large_array() {
+        let mut array = pseudo_random(100_001, u64::MAX);
+        let mut sorted = array.clone();
+        sorted.sort();
+
+        nth_element(array.as_mut_slice(), 50_000);
+
+        assert_selected(&array, 50_000, &sorted);
+    }
+
+    #[test]
+    fn exhausted_limit_falls_back_to_median_of_medians() {
+        let mut array = pseudo_random(10_000, 500);
+        let mut sorted = array.clone();
+        sorted.sort();
+
+        introselect(
+            array.as_mut_slice(),
+            1_234,
+            0,
+            &mut Probed::new(partial_compare, NoProbe),
+        );
+
+        assert_selected(&array, 1_234, &sorted);
+    }
+
+    #[test]
+    fn nth_element_by_comparator_and_key() {
+        let mut array_1 = pseudo_random(500, 100);
+        let mut array_2: Vec<(u64, usize)> = array_1.iter().map(|value| (*value, 0)).collect();
+        let mut sorted = array_1.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+
+        nth_element_by(array_1.as_mut_slice(), 10, |a, b| b.cmp(a));
+        nth_element_by_key(array_2.as_mut_slice(), 490, |(value, _)| *value);
+
+        assert_eq!(array_1[10], sorted[10]);
+        assert_eq!(array_2[490].0, sorted[9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for a slice of length 3")]
+    fn nth_element_rejects_out_of_bounds_index() {
+        nth_element(&mut [3, 1, 2], 3);
+    }
+
+    #[test]
+    fn partial_sort_sorts_the_smallest_prefix() {
+        let array = pseudo_random(1_000, 200);
+        let mut sorted = array.clone();
+        sorted.sort();
+
+        for k in [0, 1, 2, 17, 500, 999, 1_000, 5_000] {
+            let mut actual = array.clone();
+            partial_sort(actual.as_mut_slice(), k);
+
+            let k = usize::min(k, array.len());
+            assert_eq!(actual[..k], sorted[..k], "k = {k}");
+
+            actual.sort();
+            assert_eq!(actual, sorted);
+        }
+    }
+
+    #[test]
+    fn partial_sort_by_key_descending() {
+        let mut array = pseudo_random(1_000, 1_000_000);
+        let mut sorted = array.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+
+        partial_sort_by_key(array.as_mut_slice(), 10, |value| std::cmp::Reverse(*value));
+
+        assert_eq!(array[..10], sorted[..10]);
+    }
+
+    #[test]
+    fn top_k_returns_smallest_items_in_order() {
+        let items = pseudo_random(10_000, 1_000_000);
+        let mut sorted = items.clone();
+        sorted.sort();
+
+        let mut array = items.clone();
+        partial_sort(array.as_mut_slice(), 25);
+        assert_eq!(top_k(items.iter().copied(), 25), array[..25]);
+        assert_eq!(top_k(items.iter().copied(), 25), sorted[..25]);
+        assert_eq!(top_k(items.iter().copied(), 20_000), sorted);
+        assert!(top_k(items.iter().copied(), 0).is_empty());
+        assert!(top_k(Vec::<u64>::new(), 3).is_empty());
+        assert_eq!(top_k(vec![3, 1, 2], usize::MAX), [1, 2, 3]);
+    }
+
+    #[test]
+    fn top_k_by_reversed_comparator_returns_greatest() {
+        let words = ["pear", "fig", "apple", "kiwi", "banana", "date"];
+
+        assert_eq!(
+            top_k_by(words, 3, |a, b| b.cmp(a)),
+            vec!["pear", "kiwi", "fig"]
+        );
+        assert_eq!(
+            top_k_by_key(words, 2, |word| word.len()),
+            vec!["fig", "pear"]
+        );
+    }
+}
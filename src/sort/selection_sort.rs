@@ -1,21 +1,77 @@
-use std::fmt::Debug;
+use std::cmp::Ordering;
 
-pub fn sort<T: PartialOrd + Debug>(array: &mut [T]) {
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_ops(array, &mut Probed::new(compare, NoProbe));
+}
+
+pub(crate) fn sort_with_ops<T>(array: &mut [T], ops: &mut impl SortOps<T>) {
     for i in 0..array.len() {
         let mut lowest = i;
 
         for j in i..array.len() {
-            if array[lowest] > array[j] {
+            if ops.compare(&array[lowest], &array[j]).is_gt() {
                 lowest = j;
             }
         }
 
         if lowest != i {
-            array.swap(i, lowest);
+            ops.swap(array, i, lowest);
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectionSort;
+
+impl<T> Sorter<T> for SelectionSort {
+    fn name(&self) -> &'static str {
+        "selection_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Quadratic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        sort_with_ops(array, &mut Probed::new(compare, probe));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::selection_sort;
@@ -52,4 +108,26 @@ mod tests {
 
         assert_eq!(array_1, array_2);
     }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        selection_sort::sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (2, "b"), (1, "d")];
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        selection_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
 }
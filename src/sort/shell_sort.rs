@@ -0,0 +1,373 @@
+use std::cmp::Ordering;
+
+use super::{
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+const CIURA: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapSequence {
+    // n/2, n/4, ..., 1
+    Shell,
+    // 1, 4, 13, 40, ... (3^k - 1) / 2
+    Knuth,
+    // 1, 8, 23, 77, 281, ... 4^k + 3 * 2^(k - 1) + 1
+    Sedgewick,
+    // 1, 4, 10, 23, 57, 132, 301, 701, 1750, then extended by a factor of 2.25
+    Ciura,
+    // 1, 4, 9, 20, 46, 103, ... ceil(h_k) where h_k = 2.25 * h_(k - 1) + 1
+    Tokuda,
+}
+
+// Yields the gaps of a sequence that are smaller than the slice length, largest
+// first, computing each one on demand so that sorting never allocates.
+#[derive(Debug, Clone)]
+pub struct Gaps {
+    sequence: GapSequence,
+    length: usize,
+    next: Option<u32>,
+}
+
+impl GapSequence {
+    pub const ALL: [GapSequence; 5] = [
+        GapSequence::Shell,
+        GapSequence::Knuth,
+        GapSequence::Sedgewick,
+        GapSequence::Ciura,
+        GapSequence::Tokuda,
+    ];
+
+    pub fn gaps(self, length: usize) -> Gaps {
+        let next = if length < 2 {
+            None
+        } else if self == GapSequence::Shell {
+            Some(length.ilog2() - 1)
+        } else {
+            let mut index = 0;
+            while self.term(index + 1, length) < length {
+                index += 1;
+            }
+
+            Some(index)
+        };
+
+        Gaps {
+            sequence: self,
+            length,
+            next,
+        }
+    }
+
+    fn term(self, index: u32, length: usize) -> usize {
+        match self {
+            GapSequence::Shell => length >> (length.ilog2() - index),
+            GapSequence::Knuth => (3usize.saturating_pow(index + 1) - 1) / 2,
+            GapSequence::Sedgewick if index == 0 => 1,
+            GapSequence::Sedgewick => 4usize
+                .saturating_pow(index)
+                .saturating_add(3usize.saturating_mul(2usize.saturating_pow(index - 1)))
+                .saturating_add(1),
+            GapSequence::Ciura => {
+                let mut term = CIURA[usize::min(index as usize, CIURA.len() - 1)] as f64;
+                for _ in CIURA.len() - 1..index as usize {
+                    term = (term * 2.25).floor();
+                }
+
+                term as usize
+            }
+            GapSequence::Tokuda => {
+                let mut term = 1.0f64;
+                for _ in 0..index {
+                    term = 2.25 * term + 1.0;
+                }
+
+                term.ceil() as usize
+            }
+        }
+    }
+}
+
+impl Iterator for Gaps {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next?;
+        self.next = index.checked_sub(1);
+
+        Some(self.sequence.term(index, self.length))
+    }
+}
+
+pub fn sort<T: PartialOrd>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by<T>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    sort_with_sequence_by(array, GapSequence::Ciura, compare);
+}
+
+pub fn sort_by_key<T, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_with_sequence<T: PartialOrd>(array: &mut [T], sequence: GapSequence) {
+    sort_with_sequence_by(array, sequence, partial_compare);
+}
+
+pub fn sort_with_sequence_by<T>(
+    array: &mut [T],
+    sequence: GapSequence,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    let length = array.len();
+    sort_with_gaps_by(array, sequence.gaps(length), compare);
+}
+
+pub fn sort_with_gaps<T: PartialOrd>(array: &mut [T], gaps: impl IntoIterator<Item = usize>) {
+    sort_with_gaps_by(array, gaps, partial_compare);
+}
+
+// Runs a gapped insertion sort for each gap in turn. The gaps must be
+// decreasing and end with 1 for the slice to come out sorted.
+pub fn sort_with_gaps_by<T>(
+    array: &mut [T],
+    gaps: impl IntoIterator<Item = usize>,
+    compare: impl FnMut(&T, &T) -> Ordering,
+) {
+    shell_sort(array, gaps, &mut Probed::new(compare, NoProbe));
+}
+
+fn shell_sort<T>(
+    array: &mut [T],
+    gaps: impl IntoIterator<Item = usize>,
+    ops: &mut impl SortOps<T>,
+) {
+    let mut last = None;
+
+    for gap in gaps {
+        assert!(gap > 0, "gaps must be greater than zero");
+
+        for i in gap..array.len() {
+            let mut j = i;
+            while j >= gap && ops.compare(&array[j - gap], &array[j]).is_gt() {
+                ops.swap(array, j - gap, j);
+                j -= gap;
+            }
+        }
+
+        last = Some(gap);
+    }
+
+    assert!(
+        array.len() < 2 || last == Some(1),
+        "the final gap must be 1, got {last:?}"
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShellSort(pub GapSequence);
+
+impl Default for ShellSort {
+    fn default() -> Self {
+        Self(GapSequence::Ciura)
+    }
+}
+
+impl<T> Sorter<T> for ShellSort {
+    fn name(&self) -> &'static str {
+        "shell_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn is_in_place(&self) -> bool {
+        true
+    }
+
+    // Only the older sequences have proven bounds; Ciura's and Tokuda's were
+    // found empirically.
+    fn worst_case(&self) -> Complexity {
+        match self.0 {
+            GapSequence::Shell => Complexity::Quadratic,
+            GapSequence::Knuth => Complexity::ThreeHalves,
+            GapSequence::Sedgewick => Complexity::FourThirds,
+            GapSequence::Ciura | GapSequence::Tokuda => Complexity::Unknown,
+        }
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Unknown
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_with_sequence_by(array, self.0, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        let length = array.len();
+        shell_sort(array, self.0.gaps(length), &mut Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::shell_sort::{self, GapSequence};
+    use crate::testing::pseudo_random;
+
+    fn assert_every_sequence_sorts(array: &[u64]) {
+        let mut expected = array.to_vec();
+        expected.sort();
+
+        for sequence in GapSequence::ALL {
+            let mut actual = array.to_vec();
+            shell_sort::sort_with_sequence(actual.as_mut_slice(), sequence);
+
+            assert_eq!(actual, expected, "{sequence:?}");
+        }
+    }
+
+    #[test]
+    fn every_sequence_sorts() {
+        assert_every_sequence_sorts(&[]);
+        assert_every_sequence_sorts(&[1]);
+        assert_every_sequence_sorts(&[2, 1]);
+        assert_every_sequence_sorts(&[10, 9, 22, 10, 15, 5, 20, 1, 2, 1]);
+        assert_every_sequence_sorts(&(0..5_000).rev().collect::<Vec<u64>>());
+        assert_every_sequence_sorts(&pseudo_random(20_000, u64::MAX));
+        assert_every_sequence_sorts(&pseudo_random(20_000, 7));
+    }
+
+    // A new, larger gap comes into use as the length passes each term, and
+    // Shell's halving gaps all change as the length passes a power of two.
+    #[test]
+    fn every_sequence_sorts_around_its_gap_boundaries() {
+        for sequence in GapSequence::ALL {
+            let terms: Vec<usize> = sequence.gaps(10_000).collect();
+            let boundaries = terms
+                .clone()
+                .into_iter()
+                .chain((1..14).map(|power| 1 << power));
+
+            for boundary in boundaries {
+                for length in [boundary - 1, boundary, boundary + 1] {
+                    let gaps: Vec<usize> = sequence.gaps(length).collect();
+                    assert!(
+                        gaps.windows(2).all(|pair| pair[0] > pair[1]),
+                        "{sequence:?} gaps for {length}: {gaps:?}"
+                    );
+                    assert!(gaps.iter().all(|gap| *gap < length));
+                    assert_eq!(gaps.last().copied(), (length > 1).then_some(1));
+                    if sequence != GapSequence::Shell {
+                        let below: Vec<usize> = terms
+                            .iter()
+                            .copied()
+                            .filter(|term| *term < length)
+                            .collect();
+                        assert_eq!(gaps, below, "{sequence:?} gaps for {length}");
+                    }
+
+                    let array = pseudo_random(length, 1_000);
+                    let mut expected = array.clone();
+                    expected.sort();
+
+                    let mut actual = array;
+                    shell_sort::sort_with_sequence(actual.as_mut_slice(), sequence);
+                    assert_eq!(actual, expected, "{sequence:?} on {length} elements");
+
+                    let mut actual: Vec<u64> = (0..length as u64).rev().collect();
+                    shell_sort::sort_with_sequence(actual.as_mut_slice(), sequence);
+                    assert!(actual.windows(2).all(|pair| pair[0] < pair[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sequences_start_with_their_known_terms() {
+        let ascending = |sequence: GapSequence| {
+            let mut gaps: Vec<usize> = sequence.gaps(5_000).collect();
+            gaps.reverse();
+            gaps
+        };
+
+        assert_eq!(
+            ascending(GapSequence::Knuth),
+            vec![1, 4, 13, 40, 121, 364, 1093, 3280]
+        );
+        assert_eq!(
+            ascending(GapSequence::Sedgewick),
+            vec![1, 8, 23, 77, 281, 1073, 4193]
+        );
+        assert_eq!(
+            ascending(GapSequence::Ciura),
+            vec![1, 4, 10, 23, 57, 132, 301, 701, 1750, 3937]
+        );
+        assert_eq!(
+            ascending(GapSequence::Tokuda),
+            vec![1, 4, 9, 20, 46, 103, 233, 525, 1182, 2660]
+        );
+        assert_eq!(
+            ascending(GapSequence::Shell),
+            vec![1, 2, 4, 9, 19, 39, 78, 156, 312, 625, 1250, 2500]
+        );
+    }
+
+    #[test]
+    fn gaps_are_empty_for_trivial_lengths() {
+        for sequence in GapSequence::ALL {
+            assert_eq!(sequence.gaps(0).count(), 0);
+            assert_eq!(sequence.gaps(1).count(), 0);
+            assert_eq!(sequence.gaps(2).collect::<Vec<usize>>(), vec![1]);
+        }
+    }
+
+    #[test]
+    fn sort_with_custom_gaps() {
+        let mut array_1 = pseudo_random(1_000, 100);
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        shell_sort::sort_with_gaps(array_1.as_mut_slice(), [31, 7, 3, 1]);
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "the final gap must be 1")]
+    fn sort_with_gaps_rejects_sequences_not_ending_in_1() {
+        shell_sort::sort_with_gaps(&mut [3, 2, 1], [2]);
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let array = pseudo_random(2_000, 50);
+        let mut expected = array.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        for sequence in GapSequence::ALL {
+            let mut actual = array.clone();
+            shell_sort::sort_with_sequence_by(actual.as_mut_slice(), sequence, |a, b| b.cmp(a));
+
+            assert_eq!(actual, expected, "{sequence:?}");
+        }
+    }
+
+    #[test]
+    fn sort_by_key_field() {
+        let mut array_1 = vec![(3, "c"), (1, "a"), (4, "d"), (2, "b")];
+
+        shell_sort::sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+    }
+}
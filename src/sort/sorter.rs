@@ -0,0 +1,189 @@
+use std::{cmp::Ordering, fmt};
+
+use super::{
+    bubble_sort::BubbleSort,
+    bucket_sort::SampleSort,
+    cycle_sort::CycleSort,
+    heap_sort::HeapSort,
+    insertion_sort::InsertionSort,
+    intro_sort::IntroSort,
+    merge_sort::{BottomUpMergeSort, MergeSort},
+    partial_compare,
+    patience::PatienceSort,
+    probe::Probe,
+    quick_sort::QuickSort,
+    selection_sort::SelectionSort,
+    shell_sort::ShellSort,
+    tim_sort::TimSort,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Linearithmic,
+    FourThirds,
+    ThreeHalves,
+    Quadratic,
+    Unknown,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notation = match self {
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::FourThirds => "O(n^(4/3))",
+            Complexity::ThreeHalves => "O(n^(3/2))",
+            Complexity::Quadratic => "O(n^2)",
+            Complexity::Unknown => "unknown",
+        };
+
+        write!(f, "{notation}")
+    }
+}
+
+// A comparison sort that can be chosen at runtime. The comparator is taken as
+// a trait object so that sorters can be stored as `Box<dyn Sorter<T>>`.
+pub trait Sorter<T> {
+    fn name(&self) -> &'static str;
+
+    fn is_stable(&self) -> bool;
+
+    fn is_in_place(&self) -> bool;
+
+    fn worst_case(&self) -> Complexity;
+
+    fn average_case(&self) -> Complexity;
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering);
+
+    // Sorts exactly like `sort_by`, reporting every comparison, swap, write and
+    // scratch allocation to the probe as it happens.
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    );
+
+    fn sort(&self, array: &mut [T])
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(array, &mut partial_compare);
+    }
+}
+
+pub fn registry<T: Clone>() -> Vec<Box<dyn Sorter<T>>> {
+    vec![
+        Box::new(BubbleSort),
+        Box::new(InsertionSort),
+        Box::new(SelectionSort),
+        Box::new(CycleSort),
+        Box::new(ShellSort::default()),
+        Box::new(MergeSort),
+        Box::new(BottomUpMergeSort),
+        Box::new(QuickSort::default()),
+        Box::new(HeapSort),
+        Box::new(IntroSort),
+        Box::new(TimSort),
+        Box::new(PatienceSort),
+        Box::new(SampleSort::default()),
+    ]
+}
+
+pub fn by_name<T: Clone>(name: &str) -> Option<Box<dyn Sorter<T>>> {
+    registry().into_iter().find(|sorter| sorter.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn every_sorter_sorts() {
+        let inputs = vec![
+            vec![],
+            vec![1],
+            vec![10, 9, 22, 10, 15, 5, 20, 1, 2, 1],
+            (0..500).collect(),
+            (0..500).rev().collect(),
+            pseudo_random(2_000, 30),
+        ];
+
+        for sorter in registry::<u64>() {
+            for input in &inputs {
+                let mut expected = input.clone();
+                expected.sort();
+
+                let mut actual = input.clone();
+                sorter.sort(actual.as_mut_slice());
+
+                assert_eq!(actual, expected, "{}", sorter.name());
+            }
+        }
+    }
+
+    #[test]
+    fn every_sorter_sorts_by_comparator() {
+        let input = pseudo_random(1_000, 100);
+        let mut expected = input.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        for sorter in registry::<u64>() {
+            let mut actual = input.clone();
+            sorter.sort_by(actual.as_mut_slice(), &mut |a, b| b.cmp(a));
+
+            assert_eq!(actual, expected, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn stable_sorters_keep_equal_keys_in_order() {
+        let input: Vec<(u64, usize)> = pseudo_random(2_000, 10)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut expected = input.clone();
+        expected.sort_by_key(|(key, _)| *key);
+
+        for sorter in registry::<(u64, usize)>() {
+            if !sorter.is_stable() {
+                continue;
+            }
+
+            let mut actual = input.clone();
+            sorter.sort_by(actual.as_mut_slice(), &mut |a, b| a.0.cmp(&b.0));
+
+            assert_eq!(actual, expected, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let sorters = registry::<u64>();
+
+        for (index, sorter) in sorters.iter().enumerate() {
+            assert!(sorters[index + 1..]
+                .iter()
+                .all(|other| other.name() != sorter.name()));
+        }
+    }
+
+    #[test]
+    fn by_name_finds_registered_sorters() {
+        let sorter = by_name::<u64>("tim_sort").unwrap();
+
+        assert_eq!(sorter.name(), "tim_sort");
+        assert!(sorter.is_stable());
+        assert!(!sorter.is_in_place());
+        assert_eq!(sorter.worst_case(), Complexity::Linearithmic);
+        assert!(by_name::<u64>("bogo_sort").is_none());
+    }
+
+    #[test]
+    fn complexity_displays_big_o_notation() {
+        assert_eq!(Complexity::Linearithmic.to_string(), "O(n log n)");
+        assert_eq!(Complexity::Quadratic.to_string(), "O(n^2)");
+    }
+}
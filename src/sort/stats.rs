@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+
+use super::{partial_compare, probe::Probe, sorter::Sorter};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: usize,
+    pub swaps: usize,
+    // Elements moved other than by a swap: written back into the slice from
+    // scratch space, or copied out into it.
+    pub moves: usize,
+    // Elements stored into the slice, two for every swap and one for every
+    // other write. This is what wears out write-limited storage.
+    pub writes: usize,
+    // The most elements held in scratch buffers at any one time. Stack usage of
+    // recursive sorts is not included.
+    pub auxiliary_memory: usize,
+}
+
+#[derive(Debug, Default)]
+struct Counter {
+    stats: SortStats,
+    in_use: usize,
+}
+
+impl<T> Probe<T> for Counter {
+    fn compare(&mut self, _a: &T, _b: &T) {
+        self.stats.comparisons += 1;
+    }
+
+    fn swap(&mut self, _array: &[T], _i: usize, _j: usize) {
+        self.stats.swaps += 1;
+        self.stats.writes += 2;
+    }
+
+    fn write(&mut self, _array: &[T], _index: usize) {
+        self.stats.moves += 1;
+        self.stats.writes += 1;
+    }
+
+    fn allocate(&mut self, elements: usize) {
+        self.stats.moves += elements;
+        self.in_use += elements;
+        self.stats.auxiliary_memory = usize::max(self.stats.auxiliary_memory, self.in_use);
+    }
+
+    fn release(&mut self, elements: usize) {
+        self.in_use -= elements;
+    }
+}
+
+pub fn measure<T: PartialOrd>(sorter: &(impl Sorter<T> + ?Sized), array: &mut [T]) -> SortStats {
+    measure_by(sorter, array, partial_compare)
+}
+
+pub fn measure_by<T>(
+    sorter: &(impl Sorter<T> + ?Sized),
+    array: &mut [T],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> SortStats {
+    let mut counter = Counter::default();
+    sorter.sort_with_probe(array, &mut compare, &mut counter);
+
+    counter.stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{
+        bubble_sort::BubbleSort, insertion_sort::InsertionSort, merge_sort::MergeSort,
+        selection_sort::SelectionSort, sorter::registry,
+    };
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn bubble_sort_makes_one_pass_over_sorted_input() {
+        let mut array: Vec<u64> = (0..100).collect();
+
+        let stats = measure(&BubbleSort, array.as_mut_slice());
+
+        assert_eq!(
+            stats,
+            SortStats {
+                comparisons: 99,
+                ..SortStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn insertion_sort_swaps_every_inversion() {
+        let mut array: Vec<u64> = (0..100).rev().collect();
+
+        let stats = measure(&InsertionSort, array.as_mut_slice());
+
+        assert_eq!(stats.comparisons, 100 * 99 / 2);
+        assert_eq!(stats.swaps, 100 * 99 / 2);
+        assert_eq!(stats.moves, 0);
+        assert!(array.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn selection_sort_swaps_at_most_once_per_position() {
+        let mut array = pseudo_random(200, 1_000);
+
+        let stats = measure(&SelectionSort, array.as_mut_slice());
+
+        assert!(stats.swaps < 200);
+        assert_eq!(stats.auxiliary_memory, 0);
+    }
+
+    #[test]
+    fn merge_sort_reports_moves_and_scratch_space() {
+        let mut array = pseudo_random(1_000, 1_000);
+
+        let stats = measure(&MergeSort, array.as_mut_slice());
+
+        assert!(stats.moves > 0);
+        assert!(stats.auxiliary_memory > 0 && stats.auxiliary_memory <= 500);
+    }
+
+    #[test]
+    fn every_sorter_is_measured_without_changing_its_result() {
+        let input = pseudo_random(2_000, 50);
+        let mut expected = input.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        for sorter in registry::<u64>() {
+            let mut calls = 0;
+            let mut actual = input.clone();
+            let stats = measure_by(sorter.as_ref(), actual.as_mut_slice(), |a, b| {
+                calls += 1;
+                b.cmp(a)
+            });
+
+            assert_eq!(actual, expected, "{}", sorter.name());
+            assert_eq!(stats.comparisons, calls, "{}", sorter.name());
+            assert!(stats.swaps + stats.moves > 0, "{}", sorter.name());
+            if sorter.is_in_place() {
+                assert_eq!(stats.auxiliary_memory, 0, "{}", sorter.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::{mem, ops::Range};
+
+use super::insertion_sort;
+
+const INSERTION_THRESHOLD: usize = 16;
+// One bucket for strings that end at the current depth plus one per byte.
+const BUCKETS: usize = 257;
+
+// Both sorts order strings byte by byte, which for `str` is the same as the
+// standard order since UTF-8 preserves code point order. Neither is stable:
+// elements are moved by swapping.
+//
+// The `_with_lcp` variants also return the longest common prefix array: entry
+// `i` is the length of the prefix shared by elements `i - 1` and `i` after
+// sorting, and entry 0 is always 0. The sorts learn most of these lengths as
+// they go, so it costs little beyond the sort itself.
+
+pub fn multikey_quick_sort<T: AsRef<[u8]>>(array: &mut [T]) {
+    multikey(array, 0, None);
+}
+
+pub fn multikey_quick_sort_with_lcp<T: AsRef<[u8]>>(array: &mut [T]) -> Vec<usize> {
+    let mut lcp = vec![0; array.len()];
+    multikey(array, 0, Some(&mut lcp));
+
+    lcp
+}
+
+pub fn msd_radix_sort<T: AsRef<[u8]>>(array: &mut [T]) {
+    msd(array, 0, None);
+}
+
+pub fn msd_radix_sort_with_lcp<T: AsRef<[u8]>>(array: &mut [T]) -> Vec<usize> {
+    let mut lcp = vec![0; array.len()];
+    msd(array, 0, Some(&mut lcp));
+
+    lcp
+}
+
+// Computes the same array directly from neighbouring elements, for slices that
+// were sorted some other way.
+pub fn lcp_array<T: AsRef<[u8]>>(array: &[T]) -> Vec<usize> {
+    let mut lcp = vec![0; array.len()];
+    for i in 1..array.len() {
+        lcp[i] = common_prefix(array[i - 1].as_ref(), array[i].as_ref());
+    }
+
+    lcp
+}
+
+// Every element of a slice handed to the helpers below shares its first `depth`
+// bytes with the others, so only what follows is ever looked at. The helpers
+// fill in `lcp[1..]` for the slice; `lcp[0]` pairs the slice with whatever
+// precedes it, which only the caller knows.
+
+// Bentley and Sedgewick's multikey quicksort: a three-way partition on the
+// byte at `depth`, after which only the middle part, whose byte matched the
+// pivot, moves on to the next byte. As in `msd`, the largest part is sorted by
+// the next turn of the loop and only the others by a call.
+fn multikey<T: AsRef<[u8]>>(mut array: &mut [T], mut depth: usize, mut lcp: Option<&mut [usize]>) {
+    loop {
+        let length = array.len();
+        if length <= INSERTION_THRESHOLD {
+            insertion(array, depth, &mut lcp);
+            return;
+        }
+
+        let pivot = median_of_three(
+            code(&array[0], depth),
+            code(&array[length / 2], depth),
+            code(&array[length - 1], depth),
+        );
+
+        let mut less = 0;
+        let mut greater = length;
+        let mut i = 0;
+        while i < greater {
+            let current = code(&array[i], depth);
+            if current < pivot {
+                array.swap(less, i);
+                less += 1;
+                i += 1;
+            } else if current > pivot {
+                greater -= 1;
+                array.swap(i, greater);
+            } else {
+                i += 1;
+            }
+        }
+
+        if less > 0 {
+            fill(&mut lcp, less..less + 1, depth);
+        }
+        if greater < length {
+            fill(&mut lcp, greater..greater + 1, depth);
+        }
+
+        // Strings that have ended are all equal, so they need no sorting.
+        let middle = if pivot == 0 {
+            fill(&mut lcp, less + 1..greater, depth);
+            less..less
+        } else {
+            less..greater
+        };
+        let parts = [
+            (0..less, depth),
+            (middle, depth + 1),
+            (greater..length, depth),
+        ];
+        let largest = (0..parts.len())
+            .max_by_key(|part| parts[*part].0.len())
+            .expect("there are three parts");
+
+        for (part, (range, depth)) in parts.iter().cloned().enumerate() {
+            if part != largest && !range.is_empty() {
+                multikey(&mut array[range.clone()], depth, sub_range(&mut lcp, range));
+            }
+        }
+
+        let (range, next_depth) = parts[largest].clone();
+        array = &mut mem::take(&mut array)[range.clone()];
+        lcp = lcp.map(|lcp| &mut lcp[range]);
+        depth = next_depth;
+    }
+}
+
+// American flag sort on strings: counts the byte at `depth`, swaps every
+// element into its bucket in place and sorts each bucket on the next byte.
+// The largest bucket is sorted by the next turn of the loop rather than by a
+// call, so every call gets at most half the elements and the stack stays
+// O(log n) deep, however many bytes the strings share.
+fn msd<T: AsRef<[u8]>>(mut array: &mut [T], mut depth: usize, mut lcp: Option<&mut [usize]>) {
+    let mut counts = [0; BUCKETS];
+
+    loop {
+        let length = array.len();
+        if length <= INSERTION_THRESHOLD {
+            insertion(array, depth, &mut lcp);
+            return;
+        }
+
+        counts.fill(0);
+        for value in array.iter() {
+            counts[code(value, depth)] += 1;
+        }
+
+        // A shared byte needs no distributing; skipping straight to the next
+        // one keeps long common prefixes from costing a pass per byte.
+        match counts.iter().position(|count| *count == length) {
+            Some(0) => {
+                fill(&mut lcp, 1..length, depth);
+                return;
+            }
+            Some(_) => {
+                depth += 1;
+                continue;
+            }
+            None => {}
+        }
+
+        let mut starts = [0; BUCKETS];
+        for bucket in 1..BUCKETS {
+            starts[bucket] = starts[bucket - 1] + counts[bucket - 1];
+        }
+        let mut next = starts;
+
+        for bucket in 0..BUCKETS {
+            let end = starts[bucket] + counts[bucket];
+            while next[bucket] < end {
+                let target = code(&array[next[bucket]], depth);
+                if target == bucket {
+                    next[bucket] += 1;
+                } else {
+                    array.swap(next[bucket], next[target]);
+                    next[target] += 1;
+                }
+            }
+        }
+
+        let largest = (1..BUCKETS)
+            .max_by_key(|bucket| counts[*bucket])
+            .expect("there are buckets past the first");
+
+        for bucket in 0..BUCKETS {
+            let range = starts[bucket]..starts[bucket] + counts[bucket];
+            if range.is_empty() {
+                continue;
+            }
+
+            if range.start > 0 {
+                fill(&mut lcp, range.start..range.start + 1, depth);
+            }
+            if bucket == 0 {
+                fill(&mut lcp, range.start + 1..range.end, depth);
+            } else if bucket != largest {
+                msd(
+                    &mut array[range.clone()],
+                    depth + 1,
+                    sub_range(&mut lcp, range),
+                );
+            }
+        }
+
+        let range = starts[largest]..starts[largest] + counts[largest];
+        array = &mut mem::take(&mut array)[range.clone()];
+        lcp = lcp.map(|lcp| &mut lcp[range]);
+        depth += 1;
+    }
+}
+
+fn insertion<T: AsRef<[u8]>>(array: &mut [T], depth: usize, lcp: &mut Option<&mut [usize]>) {
+    insertion_sort::sort_by(array, |a, b| a.as_ref()[depth..].cmp(&b.as_ref()[depth..]));
+
+    if let Some(lcp) = lcp {
+        for i in 1..array.len() {
+            lcp[i] =
+                depth + common_prefix(&array[i - 1].as_ref()[depth..], &array[i].as_ref()[depth..]);
+        }
+    }
+}
+
+// The byte at `depth` shifted up by one, with 0 meaning the string has ended,
+// so shorter strings sort before their extensions.
+fn code<T: AsRef<[u8]>>(value: &T, depth: usize) -> usize {
+    value
+        .as_ref()
+        .get(depth)
+        .map_or(0, |byte| *byte as usize + 1)
+}
+
+fn median_of_three(a: usize, b: usize, c: usize) -> usize {
+    usize::max(usize::min(a, b), usize::min(usize::max(a, b), c))
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn sub_range<'a>(
+    lcp: &'a mut Option<&mut [usize]>,
+    range: Range<usize>,
+) -> Option<&'a mut [usize]> {
+    lcp.as_deref_mut().map(|lcp| &mut lcp[range])
+}
+
+fn fill(lcp: &mut Option<&mut [usize]>, range: Range<usize>, value: usize) {
+    if let Some(lcp) = lcp {
+        lcp[range].fill(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    // Short words over a small alphabet, so there are plenty of duplicates,
+    // shared prefixes and prefixes of other words, behind an optional long
+    // common prefix.
+    fn words(count: usize, prefix: &str) -> Vec<String> {
+        let letters = pseudo_random(count * 8, 3);
+        let lengths = pseudo_random(count, 8);
+
+        (0..count)
+            .map(|word| {
+                let letters = &letters[word * 8..word * 8 + lengths[word] as usize];
+                prefix
+                    .chars()
+                    .chain(letters.iter().map(|letter| (b'a' + *letter as u8) as char))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn assert_sorts(input: &[String]) {
+        let mut expected = input.to_vec();
+        expected.sort();
+        let expected_lcp = lcp_array(&expected);
+
+        let mut actual = input.to_vec();
+        multikey_quick_sort(actual.as_mut_slice());
+        assert_eq!(actual, expected);
+
+        let mut actual = input.to_vec();
+        msd_radix_sort(actual.as_mut_slice());
+        assert_eq!(actual, expected);
+
+        let mut actual = input.to_vec();
+        assert_eq!(
+            multikey_quick_sort_with_lcp(actual.as_mut_slice()),
+            expected_lcp
+        );
+        assert_eq!(actual, expected);
+
+        let mut actual = input.to_vec();
+        assert_eq!(msd_radix_sort_with_lcp(actual.as_mut_slice()), expected_lcp);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sort_random_words() {
+        for count in [0, 1, 2, 15, 16, 17, 100, 5_000] {
+            assert_sorts(&words(count, ""));
+        }
+    }
+
+    #[test]
+    fn sort_words_with_long_common_prefix() {
+        assert_sorts(&words(2_000, "https://example.com/some/long/path/"));
+    }
+
+    #[test]
+    fn sort_duplicates_and_empty_strings() {
+        let mut input = vec![String::new(); 40];
+        input.extend(vec!["same".to_string(); 40]);
+        input.extend(words(40, ""));
+
+        assert_sorts(&input);
+    }
+
+    #[test]
+    fn sort_unicode_in_code_point_order() {
+        let input: Vec<String> = [
+            "zebra", "émigré", "apple", "Ωmega", "éclair", "日本", "a", "Zoo", "ápice",
+        ]
+        .iter()
+        .cycle()
+        .take(60)
+        .map(|word| word.to_string())
+        .collect();
+
+        assert_sorts(&input);
+    }
+
+    #[test]
+    fn sort_byte_slices_with_zero_and_high_bytes() {
+        let mut input: Vec<&[u8]> = vec![
+            b"a\0", b"a", b"\xff", b"", b"\0", b"a\0\0", b"a\xff", b"\0\0",
+        ];
+        input = input.into_iter().cycle().take(100).collect();
+        let mut expected = input.clone();
+        expected.sort();
+
+        let lcp = msd_radix_sort_with_lcp(input.as_mut_slice());
+        assert_eq!(input, expected);
+        assert_eq!(lcp, lcp_array(&expected));
+
+        input.reverse();
+        let lcp = multikey_quick_sort_with_lcp(input.as_mut_slice());
+        assert_eq!(input, expected);
+        assert_eq!(lcp, lcp_array(&expected));
+    }
+
+    // Every suffix of a run of one letter is a prefix of the next longer one,
+    // so each byte only splits off the suffix that has just ended.
+    #[test]
+    fn sort_suffixes_of_a_long_repeated_string() {
+        let text = "a".repeat(8_000);
+        let mut suffixes: Vec<&str> = (0..text.len()).map(|start| &text[start..]).collect();
+        let mut expected = suffixes.clone();
+        expected.reverse();
+        let expected_lcp: Vec<usize> = (0..text.len()).collect();
+
+        let mut actual = suffixes.clone();
+        let lcp = msd_radix_sort_with_lcp(actual.as_mut_slice());
+        assert_eq!(actual, expected);
+        assert_eq!(lcp, expected_lcp);
+
+        let lcp = multikey_quick_sort_with_lcp(suffixes.as_mut_slice());
+        assert_eq!(suffixes, expected);
+        assert_eq!(lcp, expected_lcp);
+    }
+
+    #[test]
+    fn lcp_array_of_suffixes() {
+        let text = "banana";
+        let mut suffixes: Vec<&str> = (0..text.len()).map(|start| &text[start..]).collect();
+
+        let lcp = multikey_quick_sort_with_lcp(suffixes.as_mut_slice());
+
+        assert_eq!(suffixes, ["a", "ana", "anana", "banana", "na", "nana"]);
+        assert_eq!(lcp, [0, 1, 3, 0, 0, 2]);
+    }
+}
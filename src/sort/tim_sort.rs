@@ -0,0 +1,651 @@
+use std::cmp::Ordering;
+
+use super::{
+    merge_sort::MergeLow,
+    partial_compare,
+    probe::{NoProbe, Probe, Probed, SortOps},
+    sorter::{Complexity, Sorter},
+};
+
+const MIN_MERGE: usize = 64;
+const MIN_GALLOP: usize = 7;
+
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    length: usize,
+}
+
+struct MergeState<'a, T, O> {
+    array: &'a mut [T],
+    runs: Vec<Run>,
+    buffer: Vec<T>,
+    min_gallop: usize,
+    ops: O,
+}
+
+pub fn sort<T: PartialOrd + Clone>(array: &mut [T]) {
+    sort_by(array, partial_compare);
+}
+
+pub fn sort_by_key<T: Clone, K: PartialOrd>(array: &mut [T], mut key: impl FnMut(&T) -> K) {
+    sort_by(array, |a, b| partial_compare(&key(a), &key(b)));
+}
+
+pub fn sort_by<T: Clone>(array: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    tim_sort(array, Probed::new(compare, NoProbe));
+}
+
+fn tim_sort<T: Clone>(array: &mut [T], mut ops: impl SortOps<T>) {
+    let length = array.len();
+    if length < 2 {
+        return;
+    }
+
+    if length < MIN_MERGE {
+        let run = count_run_and_make_ascending(array, &mut ops);
+        binary_insertion_sort(array, run, &mut ops);
+        return;
+    }
+
+    let min_run = min_run_length(length);
+    let mut state = MergeState {
+        array,
+        runs: Vec::new(),
+        buffer: Vec::new(),
+        min_gallop: MIN_GALLOP,
+        ops,
+    };
+
+    let mut start = 0;
+    while start < length {
+        let mut run = count_run_and_make_ascending(&mut state.array[start..], &mut state.ops);
+
+        if run < min_run {
+            let forced = usize::min(min_run, length - start);
+            binary_insertion_sort(&mut state.array[start..start + forced], run, &mut state.ops);
+            run = forced;
+        }
+
+        state.runs.push(Run { start, length: run });
+        state.merge_collapse();
+
+        start += run;
+    }
+
+    state.merge_force_collapse();
+    state.ops.clear_buffer(&mut state.buffer);
+}
+
+// Chooses a run length in `[MIN_MERGE / 2, MIN_MERGE]` such that `length /
+// min_run` is a power of two or slightly less, which keeps the final merges
+// balanced.
+fn min_run_length(mut length: usize) -> usize {
+    let mut remainder = 0;
+
+    while length >= MIN_MERGE {
+        remainder |= length & 1;
+        length >>= 1;
+    }
+
+    length + remainder
+}
+
+// Returns the length of the run at the start of the slice. Descending runs are
+// only taken when strictly descending, so reversing them keeps the sort stable.
+fn count_run_and_make_ascending<T>(array: &mut [T], ops: &mut impl SortOps<T>) -> usize {
+    if array.len() < 2 {
+        return array.len();
+    }
+
+    let mut end = 2;
+    if ops.compare(&array[1], &array[0]).is_lt() {
+        while end < array.len() && ops.compare(&array[end], &array[end - 1]).is_lt() {
+            end += 1;
+        }
+
+        ops.reverse(&mut array[..end]);
+    } else {
+        while end < array.len() && ops.compare(&array[end], &array[end - 1]).is_ge() {
+            end += 1;
+        }
+    }
+
+    end
+}
+
+// Sorts the slice given that `array[..sorted]` is already sorted, inserting
+// each remaining element after any equal keys.
+fn binary_insertion_sort<T>(array: &mut [T], sorted: usize, ops: &mut impl SortOps<T>) {
+    for i in usize::max(sorted, 1)..array.len() {
+        let (sorted, rest) = array.split_at(i);
+        let key = &rest[0];
+        let position = gallop(sorted, sorted.len() / 2, |value| {
+            ops.compare(key, value).is_ge()
+        });
+
+        ops.rotate_right(&mut array[position..=i]);
+    }
+}
+
+// Given a slice where `before` holds for a prefix and fails for the rest,
+// returns the length of that prefix. Searches outwards from `hint` in
+// exponentially growing steps, then binary searches the bracketed range, so
+// the cost is logarithmic in the distance from the hint rather than in the
+// slice length.
+fn gallop<T>(slice: &[T], hint: usize, mut before: impl FnMut(&T) -> bool) -> usize {
+    if slice.is_empty() {
+        return 0;
+    }
+
+    let (mut low, mut high);
+
+    if before(&slice[hint]) {
+        let mut last = hint;
+        let mut offset = 1;
+        while hint + offset < slice.len() && before(&slice[hint + offset]) {
+            last = hint + offset;
+            offset = offset * 2 + 1;
+        }
+
+        low = last + 1;
+        high = usize::min(hint + offset, slice.len());
+    } else {
+        let mut last = hint;
+        let mut offset = 1;
+        while offset <= hint && !before(&slice[hint - offset]) {
+            last = hint - offset;
+            offset = offset * 2 + 1;
+        }
+
+        low = if offset <= hint { hint - offset + 1 } else { 0 };
+        high = last;
+    }
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if before(&slice[middle]) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+impl<T: Clone, O: SortOps<T>> MergeState<'_, T, O> {
+    // Merges runs until the stack satisfies, for its top four runs A, B, C, D:
+    // `B > C + D`, `A > B + C` and `C > D`. Checking the fourth run closes the
+    // gap found in the original TimSort proof.
+    fn merge_collapse(&mut self) {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            let length = |index: usize| self.runs[index].length;
+
+            if (n > 0 && length(n - 1) <= length(n) + length(n + 1))
+                || (n > 1 && length(n - 2) <= length(n - 1) + length(n))
+            {
+                if length(n - 1) < length(n + 1) {
+                    n -= 1;
+                }
+            } else if length(n) > length(n + 1) {
+                break;
+            }
+
+            self.merge_at(n);
+        }
+    }
+
+    fn merge_force_collapse(&mut self) {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            if n > 0 && self.runs[n - 1].length < self.runs[n + 1].length {
+                n -= 1;
+            }
+
+            self.merge_at(n);
+        }
+    }
+
+    fn merge_at(&mut self, index: usize) {
+        let first = self.runs[index];
+        let second = self.runs[index + 1];
+
+        self.runs[index].length += second.length;
+        self.runs.remove(index + 1);
+
+        let ops = &mut self.ops;
+        let merged = &mut self.array[first.start..second.start + second.length];
+        let (left, right) = merged.split_at(first.length);
+
+        // Elements of the first run that are already no greater than the start
+        // of the second run, and elements of the second run already greater
+        // than the end of the first, are in their final place.
+        let skip = gallop(left, 0, |value| ops.compare(&right[0], value).is_ge());
+        if skip == left.len() {
+            return;
+        }
+
+        let last_left = &left[left.len() - 1];
+        let keep = gallop(right, right.len() - 1, |value| {
+            ops.compare(value, last_left).is_lt()
+        });
+
+        let merged = &mut merged[skip..first.length + keep];
+        let middle = first.length - skip;
+
+        if middle <= keep {
+            merge_low(merged, middle, &mut self.buffer, &mut self.min_gallop, ops);
+        } else {
+            merge_high(merged, middle, &mut self.buffer, &mut self.min_gallop, ops);
+        }
+    }
+}
+
+// Merges `array[..middle]` and `array[middle..]` front to back, copying the
+// shorter left run into the buffer. Switches to galloping once one side has
+// won `min_gallop` times in a row.
+fn merge_low<T: Clone>(
+    array: &mut [T],
+    middle: usize,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+    ops: &mut impl SortOps<T>,
+) {
+    ops.fill_buffer(buffer, &array[..middle]);
+
+    let length = array.len();
+    let mut merge = MergeLow {
+        array,
+        buffer,
+        left: 0,
+        right: middle,
+        write: 0,
+    };
+
+    'merge: loop {
+        let mut left_wins = 0;
+        let mut right_wins = 0;
+
+        loop {
+            if merge.left == merge.buffer.len() || merge.right == length {
+                break 'merge;
+            }
+
+            if ops
+                .compare(&merge.array[merge.right], &merge.buffer[merge.left])
+                .is_lt()
+            {
+                ops.swap(merge.array, merge.write, merge.right);
+                merge.right += 1;
+                right_wins += 1;
+                left_wins = 0;
+            } else {
+                ops.write(merge.array, merge.write, merge.buffer[merge.left].clone());
+                merge.left += 1;
+                left_wins += 1;
+                right_wins = 0;
+            }
+            merge.write += 1;
+
+            if left_wins >= *min_gallop || right_wins >= *min_gallop {
+                break;
+            }
+        }
+
+        loop {
+            if merge.left == merge.buffer.len() || merge.right == length {
+                break 'merge;
+            }
+
+            let next = &merge.array[merge.right];
+            left_wins = gallop(&merge.buffer[merge.left..], 0, |value| {
+                ops.compare(next, value).is_ge()
+            });
+            for _ in 0..left_wins {
+                ops.write(merge.array, merge.write, merge.buffer[merge.left].clone());
+                merge.left += 1;
+                merge.write += 1;
+            }
+
+            if merge.left == merge.buffer.len() {
+                break 'merge;
+            }
+
+            let next = &merge.buffer[merge.left];
+            right_wins = gallop(&merge.array[merge.right..], 0, |value| {
+                ops.compare(value, next).is_lt()
+            });
+            for _ in 0..right_wins {
+                ops.swap(merge.array, merge.write, merge.right);
+                merge.right += 1;
+                merge.write += 1;
+            }
+
+            *min_gallop = usize::max(*min_gallop - 1, 1);
+            if left_wins < MIN_GALLOP && right_wins < MIN_GALLOP {
+                break;
+            }
+        }
+
+        *min_gallop += 2;
+    }
+
+    while merge.left < merge.buffer.len() {
+        ops.write(merge.array, merge.write, merge.buffer[merge.left].clone());
+        merge.left += 1;
+        merge.write += 1;
+    }
+}
+
+// Mirror image of `merge_low`: copies the shorter right run into the buffer and
+// merges back to front.
+fn merge_high<T: Clone>(
+    array: &mut [T],
+    middle: usize,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+    ops: &mut impl SortOps<T>,
+) {
+    ops.fill_buffer(buffer, &array[middle..]);
+
+    let mut merge = MergeHigh {
+        left: middle,
+        right: buffer.len(),
+        write: array.len(),
+        array,
+        buffer,
+    };
+
+    'merge: loop {
+        let mut left_wins = 0;
+        let mut right_wins = 0;
+
+        loop {
+            if merge.left == 0 || merge.right == 0 {
+                break 'merge;
+            }
+
+            if ops
+                .compare(&merge.buffer[merge.right - 1], &merge.array[merge.left - 1])
+                .is_lt()
+            {
+                ops.swap(merge.array, merge.write - 1, merge.left - 1);
+                merge.left -= 1;
+                left_wins += 1;
+                right_wins = 0;
+            } else {
+                ops.write(
+                    merge.array,
+                    merge.write - 1,
+                    merge.buffer[merge.right - 1].clone(),
+                );
+                merge.right -= 1;
+                right_wins += 1;
+                left_wins = 0;
+            }
+            merge.write -= 1;
+
+            if left_wins >= *min_gallop || right_wins >= *min_gallop {
+                break;
+            }
+        }
+
+        loop {
+            if merge.left == 0 || merge.right == 0 {
+                break 'merge;
+            }
+
+            let next = &merge.array[merge.left - 1];
+            right_wins = merge.right
+                - gallop(&merge.buffer[..merge.right], merge.right - 1, |value| {
+                    ops.compare(value, next).is_lt()
+                });
+            for _ in 0..right_wins {
+                ops.write(
+                    merge.array,
+                    merge.write - 1,
+                    merge.buffer[merge.right - 1].clone(),
+                );
+                merge.right -= 1;
+                merge.write -= 1;
+            }
+
+            if merge.right == 0 {
+                break 'merge;
+            }
+
+            let next = &merge.buffer[merge.right - 1];
+            left_wins = merge.left
+                - gallop(&merge.array[..merge.left], merge.left - 1, |value| {
+                    ops.compare(next, value).is_ge()
+                });
+            for _ in 0..left_wins {
+                ops.swap(merge.array, merge.write - 1, merge.left - 1);
+                merge.left -= 1;
+                merge.write -= 1;
+            }
+
+            *min_gallop = usize::max(*min_gallop - 1, 1);
+            if left_wins < MIN_GALLOP && right_wins < MIN_GALLOP {
+                break;
+            }
+        }
+
+        *min_gallop += 2;
+    }
+
+    while merge.right > 0 {
+        ops.write(
+            merge.array,
+            merge.write - 1,
+            merge.buffer[merge.right - 1].clone(),
+        );
+        merge.right -= 1;
+        merge.write -= 1;
+    }
+}
+
+// Mirror image of `MergeLow`: the merged output fills the slice from `write`
+// to the end, and `array[left..write]` holds stale copies of the buffered
+// elements still to be merged, `buffer[..right]`.
+struct MergeHigh<'a, T: Clone> {
+    array: &'a mut [T],
+    buffer: &'a [T],
+    left: usize,
+    right: usize,
+    write: usize,
+}
+
+impl<T: Clone> Drop for MergeHigh<'_, T> {
+    fn drop(&mut self) {
+        self.array[self.left..self.write].clone_from_slice(&self.buffer[..self.right]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimSort;
+
+impl<T: Clone> Sorter<T> for TimSort {
+    fn name(&self) -> &'static str {
+        "tim_sort"
+    }
+
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn is_in_place(&self) -> bool {
+        false
+    }
+
+    fn worst_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort_by(&self, array: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        sort_by(array, compare);
+    }
+
+    fn sort_with_probe(
+        &self,
+        array: &mut [T],
+        compare: &mut dyn FnMut(&T, &T) -> Ordering,
+        probe: &mut dyn Probe<T>,
+    ) {
+        tim_sort(array, Probed::new(compare, probe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::conformance::Keyed;
+    use super::*;
+    use crate::testing::pseudo_random;
+
+    fn assert_sorts(array: Vec<u64>) {
+        let mut array_1 = array;
+        let mut array_2 = array_1.clone();
+
+        array_2.sort();
+        sort(array_1.as_mut_slice());
+
+        assert_eq!(array_1, array_2);
+    }
+
+    fn assert_stable(keys: Vec<u64>) {
+        let mut array = Keyed::tag(keys);
+        let mut expected: Vec<(u64, usize)> =
+            array.iter().map(|keyed| (keyed.key, keyed.index)).collect();
+
+        expected.sort_by_key(|(key, _)| *key);
+        sort(array.as_mut_slice());
+
+        let actual: Vec<(u64, usize)> =
+            array.iter().map(|keyed| (keyed.key, keyed.index)).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sort_empty_and_single_element_arrays() {
+        assert_sorts(vec![]);
+        assert_sorts(vec![1]);
+    }
+
+    #[test]
+    fn sort_large_arrays() {
+        assert_sorts(pseudo_random(100_000, u64::MAX));
+        assert_sorts(pseudo_random(100_000, 10));
+        assert_sorts((0..10_000).rev().collect());
+        assert_sorts((0..5_000).chain((0..5_000).rev()).collect());
+    }
+
+    #[test]
+    fn sort_arrays_made_of_natural_runs() {
+        let mut array = Vec::new();
+        for (index, length) in pseudo_random(200, 500).into_iter().enumerate() {
+            let run = (0..length).map(|value| value * 3 + index as u64 % 7);
+            if index % 2 == 0 {
+                array.extend(run);
+            } else {
+                array.extend(run.rev());
+            }
+        }
+
+        assert_sorts(array);
+    }
+
+    #[test]
+    fn sort_interleaved_blocks_that_trigger_galloping() {
+        let mut array: Vec<u64> = Vec::new();
+        for block in 0..50 {
+            array.extend((0..100).map(|value| block * 1_000 + value));
+        }
+        for block in 0..50 {
+            array.extend((0..100).map(|value| block * 1_000 + 500 + value));
+        }
+
+        assert_sorts(array);
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        assert_stable(pseudo_random(50, 5));
+        assert_stable(pseudo_random(10_000, 50));
+        assert_stable((0..5_000).map(|value| (5_000 - value) / 10).collect());
+        assert_stable(
+            (0..3_000)
+                .map(|value| value / 4)
+                .chain((0..3_000).map(|value| value / 6))
+                .collect(),
+        );
+    }
+
+    #[test]
+    fn descending_runs_with_equal_keys_are_not_reversed() {
+        let mut array = vec![5, 4, 4, 3, 1];
+
+        let run = count_run_and_make_ascending(
+            array.as_mut_slice(),
+            &mut Probed::new(partial_compare, NoProbe),
+        );
+
+        assert_eq!(run, 2);
+        assert_eq!(array, vec![4, 5, 4, 3, 1]);
+    }
+
+    #[test]
+    fn min_run_length_stays_within_bounds() {
+        assert_eq!(min_run_length(63), 63);
+        assert_eq!(min_run_length(64), 32);
+        assert_eq!(min_run_length(65), 33);
+
+        for length in 64..10_000 {
+            let min_run = min_run_length(length);
+            assert!((MIN_MERGE / 2..=MIN_MERGE).contains(&min_run));
+        }
+    }
+
+    #[test]
+    fn gallop_finds_prefix_length_from_any_hint() {
+        let array: Vec<u64> = (0..100).collect();
+
+        for hint in [0, 17, 50, 99] {
+            for key in [0, 1, 42, 99, 100] {
+                let count = gallop(&array, hint, |value| *value < key);
+                assert_eq!(count, key as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_by_descending_order() {
+        let mut array_1 = pseudo_random(10_000, 100);
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by(|a, b| b.cmp(a));
+        sort_by(array_1.as_mut_slice(), |a, b| b.cmp(a));
+
+        assert_eq!(array_1, array_2);
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut array_1: Vec<(u64, usize)> = pseudo_random(10_000, 20)
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let mut array_2 = array_1.clone();
+
+        array_2.sort_by_key(|(key, _)| *key);
+        sort_by_key(array_1.as_mut_slice(), |(key, _)| *key);
+
+        assert_eq!(array_1, array_2);
+    }
+}
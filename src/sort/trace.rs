@@ -0,0 +1,406 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{self, Write},
+    mem,
+};
+
+use super::{partial_compare, probe::Probe, sorter::Sorter};
+
+// Where a compared element lives: in the slice being sorted, or in a scratch
+// buffer such as the one merge sort copies runs into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Array(usize),
+    Scratch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Start,
+    Compare(Slot, Slot),
+    Swap(usize, usize),
+    Write(usize),
+}
+
+// An event together with the whole array as it looked right after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<T> {
+    pub event: Event,
+    pub array: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace<T> {
+    pub steps: Vec<Step<T>>,
+}
+
+pub fn record<T: PartialOrd + Clone>(
+    sorter: &(impl Sorter<T> + ?Sized),
+    array: &mut [T],
+) -> Trace<T> {
+    record_by(sorter, array, partial_compare)
+}
+
+pub fn record_by<T: Clone>(
+    sorter: &(impl Sorter<T> + ?Sized),
+    array: &mut [T],
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Trace<T> {
+    let mut recorder = Recorder {
+        base: array.as_ptr() as usize,
+        mirror: array.to_vec(),
+        steps: vec![Step {
+            event: Event::Start,
+            array: array.to_vec(),
+        }],
+    };
+    sorter.sort_with_probe(array, &mut compare, &mut recorder);
+
+    Trace {
+        steps: recorder.steps,
+    }
+}
+
+// Probes only see the sub-slice an algorithm is working on, so the recorder
+// keeps its own copy of the whole array and uses element addresses to turn
+// sub-slice indices back into positions in the original slice.
+struct Recorder<T> {
+    base: usize,
+    mirror: Vec<T>,
+    steps: Vec<Step<T>>,
+}
+
+impl<T: Clone> Recorder<T> {
+    fn position(&self, element: *const T) -> Option<usize> {
+        let size = mem::size_of::<T>().max(1);
+        let offset = (element as usize).wrapping_sub(self.base) / size;
+
+        (offset < self.mirror.len()).then_some(offset)
+    }
+
+    fn absolute(&self, array: &[T], index: usize) -> usize {
+        self.position(array.as_ptr()).unwrap_or(0) + index
+    }
+
+    fn push(&mut self, event: Event) {
+        self.steps.push(Step {
+            event,
+            array: self.mirror.clone(),
+        });
+    }
+}
+
+impl<T: Clone> Probe<T> for Recorder<T> {
+    fn compare(&mut self, a: &T, b: &T) {
+        let slot = |element| self.position(element).map_or(Slot::Scratch, Slot::Array);
+        let event = Event::Compare(slot(a), slot(b));
+        self.push(event);
+    }
+
+    fn swap(&mut self, array: &[T], i: usize, j: usize) {
+        let (i, j) = (self.absolute(array, i), self.absolute(array, j));
+        self.mirror.swap(i, j);
+        self.push(Event::Swap(i, j));
+    }
+
+    fn write(&mut self, array: &[T], index: usize) {
+        let absolute = self.absolute(array, index);
+        self.mirror[absolute] = array[index].clone();
+        self.push(Event::Write(absolute));
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slot::Array(index) => write!(f, "{index}"),
+            Slot::Scratch => write!(f, "-"),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Start => write!(f, "start"),
+            Event::Compare(a, b) => write!(f, "compare {a} {b}"),
+            Event::Swap(i, j) => write!(f, "swap {i} {j}"),
+            Event::Write(index) => write!(f, "write {index}"),
+        }
+    }
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::Start => "start",
+            Event::Compare(..) => "compare",
+            Event::Swap(..) => "swap",
+            Event::Write(..) => "write",
+        }
+    }
+
+    fn indices(&self) -> Vec<Option<usize>> {
+        let slot = |slot: &Slot| match slot {
+            Slot::Array(index) => Some(*index),
+            Slot::Scratch => None,
+        };
+
+        match self {
+            Event::Start => vec![],
+            Event::Compare(a, b) => vec![slot(a), slot(b)],
+            Event::Swap(i, j) => vec![Some(*i), Some(*j)],
+            Event::Write(index) => vec![Some(*index)],
+        }
+    }
+}
+
+impl<T: fmt::Display> Trace<T> {
+    // One step per line: the event, then the array after it, e.g.
+    // `swap 0 1 | 3 5 1`. Scratch buffer slots are shown as `-`.
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        for step in &self.steps {
+            write!(out, "{} |", step.event)?;
+            for value in &step.array {
+                write!(out, " {value}")?;
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: JsonValue> Trace<T> {
+    // One JSON object per line, e.g.
+    // `{"step":1,"event":"swap","indices":[0,1],"array":[3,5,1]}`. Scratch
+    // buffer slots are written as `null`.
+    pub fn write_json_lines(&self, out: &mut impl Write) -> io::Result<()> {
+        for (number, step) in self.steps.iter().enumerate() {
+            let indices: Vec<String> = step
+                .event
+                .indices()
+                .iter()
+                .map(|index| index.map_or("null".to_string(), |index| index.to_string()))
+                .collect();
+            let array: Vec<String> = step.array.iter().map(JsonValue::to_json).collect();
+
+            writeln!(
+                out,
+                r#"{{"step":{number},"event":"{}","indices":[{}],"array":[{}]}}"#,
+                step.event.name(),
+                indices.join(","),
+                array.join(",")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait JsonValue {
+    fn to_json(&self) -> String;
+}
+
+macro_rules! impl_json_number {
+    ($($type:ty),*) => {
+        $(
+            impl JsonValue for $type {
+                fn to_json(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_json_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool);
+
+macro_rules! impl_json_float {
+    ($($type:ty),*) => {
+        $(
+            // JSON has no representation for NaN or the infinities.
+            impl JsonValue for $type {
+                fn to_json(&self) -> String {
+                    if self.is_finite() {
+                        self.to_string()
+                    } else {
+                        "null".to_string()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_json_float!(f32, f64);
+
+impl JsonValue for str {
+    fn to_json(&self) -> String {
+        let mut json = String::with_capacity(self.len() + 2);
+        json.push('"');
+        for character in self.chars() {
+            match character {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                '\n' => json.push_str("\\n"),
+                '\r' => json.push_str("\\r"),
+                '\t' => json.push_str("\\t"),
+                character if character.is_control() => {
+                    json.push_str(&format!("\\u{:04x}", character as u32));
+                }
+                character => json.push(character),
+            }
+        }
+        json.push('"');
+
+        json
+    }
+}
+
+impl JsonValue for String {
+    fn to_json(&self) -> String {
+        self.as_str().to_json()
+    }
+}
+
+impl JsonValue for char {
+    fn to_json(&self) -> String {
+        self.to_string().to_json()
+    }
+}
+
+impl<T: JsonValue + ?Sized> JsonValue for &T {
+    fn to_json(&self) -> String {
+        (**self).to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{insertion_sort::InsertionSort, merge_sort::MergeSort, sorter::registry};
+    use crate::testing::pseudo_random;
+
+    #[test]
+    fn records_every_step_of_insertion_sort() {
+        let mut array = vec![3, 1, 2];
+
+        let trace = record(&InsertionSort, array.as_mut_slice());
+        let steps: Vec<(Event, Vec<i32>)> = trace
+            .steps
+            .into_iter()
+            .map(|step| (step.event, step.array))
+            .collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                (Event::Start, vec![3, 1, 2]),
+                (
+                    Event::Compare(Slot::Array(0), Slot::Array(1)),
+                    vec![3, 1, 2]
+                ),
+                (Event::Swap(0, 1), vec![1, 3, 2]),
+                (
+                    Event::Compare(Slot::Array(1), Slot::Array(2)),
+                    vec![1, 3, 2]
+                ),
+                (Event::Swap(1, 2), vec![1, 2, 3]),
+                (
+                    Event::Compare(Slot::Array(0), Slot::Array(1)),
+                    vec![1, 2, 3]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_sort_compares_against_scratch_and_writes_back() {
+        let mut array = vec![2, 3, 1];
+
+        let trace = record(&MergeSort, array.as_mut_slice());
+
+        assert!(trace
+            .steps
+            .iter()
+            .any(|step| matches!(step.event, Event::Compare(Slot::Array(_), Slot::Scratch))));
+        assert!(trace
+            .steps
+            .iter()
+            .any(|step| matches!(step.event, Event::Write(_))));
+    }
+
+    #[test]
+    fn every_sorter_replays_to_the_sorted_array() {
+        let input = pseudo_random(300, 40);
+        let mut expected = input.clone();
+        expected.sort();
+
+        for sorter in registry::<u64>() {
+            let mut array = input.clone();
+            let trace = record(sorter.as_ref(), array.as_mut_slice());
+
+            assert_eq!(trace.steps[0].array, input, "{}", sorter.name());
+            assert_eq!(
+                trace.steps.last().unwrap().array,
+                expected,
+                "{}",
+                sorter.name()
+            );
+        }
+    }
+
+    #[test]
+    fn text_export_has_one_line_per_step() {
+        let mut array = vec![2, 1];
+        let trace = record(&InsertionSort, array.as_mut_slice());
+
+        let mut text = Vec::new();
+        trace.write_text(&mut text).unwrap();
+
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "start | 2 1\ncompare 0 1 | 2 1\nswap 0 1 | 1 2\n"
+        );
+    }
+
+    #[test]
+    fn json_lines_export_writes_one_object_per_step() {
+        let mut array = vec!["b", "a"];
+        let trace = record(&MergeSort, array.as_mut_slice());
+
+        let mut json = Vec::new();
+        trace.write_json_lines(&mut json).unwrap();
+
+        let lines: Vec<String> = String::from_utf8(json)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            lines[0],
+            r#"{"step":0,"event":"start","indices":[],"array":["b","a"]}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"step":1,"event":"compare","indices":[0,1],"array":["b","a"]}"#
+        );
+        assert_eq!(
+            lines.last().unwrap(),
+            &format!(
+                r#"{{"step":{},"event":"write","indices":[1],"array":["a","b"]}}"#,
+                lines.len() - 1
+            )
+        );
+    }
+
+    #[test]
+    fn json_values_are_escaped() {
+        assert_eq!("say \"hi\"\n".to_json(), r#""say \"hi\"\n""#);
+        assert_eq!(f64::NAN.to_json(), "null");
+        assert_eq!((-1.5f64).to_json(), "-1.5");
+        assert_eq!('\u{1}'.to_json(), r#""\u0001""#);
+    }
+}
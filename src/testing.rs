@@ -0,0 +1,13 @@
+use crate::sort::random::XorShift;
+
+// Helpers shared by the test modules across the crate.
+
+const SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+// The same values on every run, so a failing input can be reproduced.
+pub(crate) fn pseudo_random(length: usize, modulus: u64) -> Vec<u64> {
+    XorShift::with_seed(SEED)
+        .take(length)
+        .map(|value| value % modulus)
+        .collect()
+}